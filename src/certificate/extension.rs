@@ -1,10 +1,14 @@
 use super::Certificate;
 
-use const_oid::AssociatedOid;
+use crate::Result;
+
+use const_oid::{AssociatedOid, ObjectIdentifier};
 use pkcs8::der::Decode;
 use x509_cert::ext::pkix::{
-    AuthorityKeyIdentifier, BasicConstraints, CertificatePolicies, ExtendedKeyUsage, KeyUsage,
-    SubjectAltName, SubjectKeyIdentifier,
+    AuthorityInfoAccessSyntax, AuthorityKeyIdentifier, BasicConstraints, CertificatePolicies,
+    CrlDistributionPoints, ExtendedKeyUsage, FreshestCrl, InhibitAnyPolicy, IssuerAltName,
+    KeyUsage, NameConstraints, PolicyConstraints, PolicyMappings, PrivateKeyUsagePeriod,
+    SubjectAltName, SubjectDirectoryAttributes, SubjectInfoAccessSyntax, SubjectKeyIdentifier,
 };
 
 pub type Extensions = Vec<Extension>;
@@ -18,12 +22,23 @@ pub enum Extension {
     AuthorityKeyIdentifier(AuthorityKeyIdentifier),
     SubjectKeyIdentifier(SubjectKeyIdentifier),
     CertificatePolicies(CertificatePolicies),
+    NameConstraints(NameConstraints),
+    PolicyMappings(PolicyMappings),
+    PolicyConstraints(PolicyConstraints),
+    InhibitAnyPolicy(InhibitAnyPolicy),
+    CrlDistributionPoints(CrlDistributionPoints),
+    FreshestCrl(FreshestCrl),
+    AuthorityInfoAccess(AuthorityInfoAccessSyntax),
+    SubjectInfoAccess(SubjectInfoAccessSyntax),
+    IssuerAlternativeName(IssuerAltName),
+    SubjectDirectoryAttributes(SubjectDirectoryAttributes),
+    PrivateKeyUsagePeriod(PrivateKeyUsagePeriod),
 }
 
 impl TryFrom<&x509_cert::ext::Extension> for Extension {
     type Error = crate::error::Error;
 
-    fn try_from(value: &x509_cert::ext::Extension) -> Result<Self, Self::Error> {
+    fn try_from(value: &x509_cert::ext::Extension) -> std::result::Result<Self, Self::Error> {
         let oid = value.extn_id;
         let bytes = value.extn_value.as_bytes();
 
@@ -41,6 +56,27 @@ impl TryFrom<&x509_cert::ext::Extension> for Extension {
             CertificatePolicies::OID => {
                 Self::CertificatePolicies(CertificatePolicies::from_der(bytes)?)
             }
+            NameConstraints::OID => Self::NameConstraints(NameConstraints::from_der(bytes)?),
+            PolicyMappings::OID => Self::PolicyMappings(PolicyMappings::from_der(bytes)?),
+            PolicyConstraints::OID => Self::PolicyConstraints(PolicyConstraints::from_der(bytes)?),
+            InhibitAnyPolicy::OID => Self::InhibitAnyPolicy(InhibitAnyPolicy::from_der(bytes)?),
+            CrlDistributionPoints::OID => {
+                Self::CrlDistributionPoints(CrlDistributionPoints::from_der(bytes)?)
+            }
+            FreshestCrl::OID => Self::FreshestCrl(FreshestCrl::from_der(bytes)?),
+            AuthorityInfoAccessSyntax::OID => {
+                Self::AuthorityInfoAccess(AuthorityInfoAccessSyntax::from_der(bytes)?)
+            }
+            SubjectInfoAccessSyntax::OID => {
+                Self::SubjectInfoAccess(SubjectInfoAccessSyntax::from_der(bytes)?)
+            }
+            IssuerAltName::OID => Self::IssuerAlternativeName(IssuerAltName::from_der(bytes)?),
+            SubjectDirectoryAttributes::OID => {
+                Self::SubjectDirectoryAttributes(SubjectDirectoryAttributes::from_der(bytes)?)
+            }
+            PrivateKeyUsagePeriod::OID => {
+                Self::PrivateKeyUsagePeriod(PrivateKeyUsagePeriod::from_der(bytes)?)
+            }
             _ => return Err(super::Error::UnsupportedExtension(oid))?,
         };
 
@@ -48,7 +84,45 @@ impl TryFrom<&x509_cert::ext::Extension> for Extension {
     }
 }
 
+impl Extension {
+    /// Whether the path validation processes the extension
+    ///
+    /// The other extensions are only decoded for the caller,
+    /// so they have to be handled by the caller if they are critical.
+    pub(crate) fn is_processed(&self) -> bool {
+        !matches!(
+            self,
+            Self::CrlDistributionPoints(_)
+                | Self::FreshestCrl(_)
+                | Self::AuthorityInfoAccess(_)
+                | Self::SubjectInfoAccess(_)
+                | Self::IssuerAlternativeName(_)
+                | Self::SubjectDirectoryAttributes(_)
+                | Self::PrivateKeyUsagePeriod(_)
+        )
+    }
+}
+
 impl Certificate {
+    /// Get the raw extension with the OID, whether it is supported or not
+    pub fn extension(&self, oid: ObjectIdentifier) -> Option<&x509_cert::ext::Extension> {
+        self.extensions().iter().find(|ext| ext.extn_id == oid)
+    }
+
+    /// Decode the extension of the given type, e.g. `cert.get::<SubjectAltName>()`
+    ///
+    /// Returns `None` if the certificate has no such extension.
+    pub fn get<'a, T>(&'a self) -> Result<Option<T>>
+    where
+        T: AssociatedOid + Decode<'a>,
+    {
+        let Some(ext) = self.extension(T::OID) else {
+            return Ok(None);
+        };
+
+        Ok(Some(T::from_der(ext.extn_value.as_bytes())?))
+    }
+
     pub fn get_basic_constraints(&self) -> Option<&BasicConstraints> {
         self.parsed_extensions.iter().find_map(|ext| match ext {
             Extension::BasicConstraints(bc) => Some(bc),
@@ -97,4 +171,81 @@ impl Certificate {
             _ => None,
         })
     }
+
+    pub fn get_name_constraints(&self) -> Option<&NameConstraints> {
+        self.parsed_extensions.iter().find_map(|ext| match ext {
+            Extension::NameConstraints(nc) => Some(nc),
+            _ => None,
+        })
+    }
+
+    pub fn get_policy_mappings(&self) -> Option<&PolicyMappings> {
+        self.parsed_extensions.iter().find_map(|ext| match ext {
+            Extension::PolicyMappings(pm) => Some(pm),
+            _ => None,
+        })
+    }
+
+    pub fn get_policy_constraints(&self) -> Option<&PolicyConstraints> {
+        self.parsed_extensions.iter().find_map(|ext| match ext {
+            Extension::PolicyConstraints(pc) => Some(pc),
+            _ => None,
+        })
+    }
+
+    pub fn get_inhibit_any_policy(&self) -> Option<&InhibitAnyPolicy> {
+        self.parsed_extensions.iter().find_map(|ext| match ext {
+            Extension::InhibitAnyPolicy(iap) => Some(iap),
+            _ => None,
+        })
+    }
+
+    pub fn get_crl_distribution_points(&self) -> Option<&CrlDistributionPoints> {
+        self.parsed_extensions.iter().find_map(|ext| match ext {
+            Extension::CrlDistributionPoints(crldp) => Some(crldp),
+            _ => None,
+        })
+    }
+
+    pub fn get_freshest_crl(&self) -> Option<&FreshestCrl> {
+        self.parsed_extensions.iter().find_map(|ext| match ext {
+            Extension::FreshestCrl(fcrl) => Some(fcrl),
+            _ => None,
+        })
+    }
+
+    pub fn get_authority_info_access(&self) -> Option<&AuthorityInfoAccessSyntax> {
+        self.parsed_extensions.iter().find_map(|ext| match ext {
+            Extension::AuthorityInfoAccess(aia) => Some(aia),
+            _ => None,
+        })
+    }
+
+    pub fn get_subject_info_access(&self) -> Option<&SubjectInfoAccessSyntax> {
+        self.parsed_extensions.iter().find_map(|ext| match ext {
+            Extension::SubjectInfoAccess(sia) => Some(sia),
+            _ => None,
+        })
+    }
+
+    pub fn get_issuer_alt_name(&self) -> Option<&IssuerAltName> {
+        self.parsed_extensions.iter().find_map(|ext| match ext {
+            Extension::IssuerAlternativeName(ian) => Some(ian),
+            _ => None,
+        })
+    }
+
+    pub fn get_subject_directory_attributes(&self) -> Option<&SubjectDirectoryAttributes> {
+        self.parsed_extensions.iter().find_map(|ext| match ext {
+            Extension::SubjectDirectoryAttributes(sda) => Some(sda),
+            _ => None,
+        })
+    }
+
+    pub fn get_private_key_usage_period(&self) -> Option<&PrivateKeyUsagePeriod> {
+        self.parsed_extensions.iter().find_map(|ext| match ext {
+            Extension::PrivateKeyUsagePeriod(pkup) => Some(pkup),
+            _ => None,
+        })
+    }
 }
@@ -0,0 +1,294 @@
+use crate::Result;
+
+use super::{Certificate, Error};
+
+use std::net::IpAddr;
+
+use const_oid::{db::rfc4519::CN, ObjectIdentifier};
+use pkcs8::der::asn1::{Ia5StringRef, PrintableStringRef, Utf8StringRef};
+use tracing::{debug, debug_span};
+use x509_cert::ext::pkix::name::GeneralName;
+
+/// SmtpUTF8Mailbox otherName for internationalized email addresses as defined in
+/// [RFC 9598 Section 3](https://www.rfc-editor.org/rfc/rfc9598#section-3)
+const SMTP_UTF8_MAILBOX: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.8.9");
+
+impl Certificate {
+    /// Verify that the certificate is valid for the host name as defined in
+    /// [RFC 9525 Section 6](https://www.rfc-editor.org/rfc/rfc9525#section-6)
+    ///
+    /// The host name may be given as U-labels or A-labels, an IP address is verified
+    /// with [`Certificate::verify_ip_address`].
+    pub fn verify_hostname(&self, hostname: &str) -> Result<()> {
+        self.verify_hostname_with(hostname, false)
+    }
+
+    /// Verify that the certificate is valid for the host name,
+    /// falling back to the subject common names if enabled and the certificate has no DNS name
+    ///
+    /// The path validation applies the dNSName constraints to the common names of such a leaf,
+    /// if they look like host names.
+    pub fn verify_hostname_with(&self, hostname: &str, common_name_fallback: bool) -> Result<()> {
+        let _span = debug_span!("verify_hostname", hostname).entered();
+
+        if let Ok(address) = hostname.parse::<IpAddr>() {
+            return self.verify_ip_address(address);
+        }
+
+        // Normalized like the names matched against dNSName constraints
+        let hostname = normalize_dns_name(hostname);
+        let reference = match idna::domain_to_ascii_strict(&hostname) {
+            Ok(reference) if !reference.is_empty() => reference,
+            _ => {
+                debug!("invalid host name");
+                return Err(Error::IdentityInvalid.into());
+            }
+        };
+
+        let dns_names: Vec<_> = self
+            .get_subject_alt_name()
+            .into_iter()
+            .flat_map(|san| san.0.iter())
+            .filter_map(|name| match name {
+                GeneralName::DnsName(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect();
+
+        // The common name is only considered without any DNS name in the certificate
+        let presented = if dns_names.is_empty() && common_name_fallback {
+            debug!("falling back to subject common name");
+            self.common_names()
+        } else {
+            dns_names
+        };
+
+        if presented
+            .into_iter()
+            .any(|presented| dns_name_matches(presented, &reference))
+        {
+            return Ok(());
+        }
+
+        debug!("no matching DNS name");
+        Err(Error::IdentityMismatch.into())
+    }
+
+    /// Verify that the certificate has an iPAddress subject alternative name for the address
+    pub fn verify_ip_address(&self, address: IpAddr) -> Result<()> {
+        let _span = debug_span!("verify_ip_address", address = %address).entered();
+
+        let matches = self
+            .get_subject_alt_name()
+            .into_iter()
+            .flat_map(|san| san.0.iter())
+            .any(|name| match name {
+                GeneralName::IpAddress(presented) => {
+                    ip_address(presented.as_bytes()) == Some(address)
+                }
+                _ => false,
+            });
+
+        if !matches {
+            debug!("no matching IP address");
+            return Err(Error::IdentityMismatch.into());
+        }
+
+        Ok(())
+    }
+
+    /// Verify that the certificate has an rfc822Name or SmtpUTF8Mailbox
+    /// subject alternative name for the email address
+    ///
+    /// The local part is compared case-sensitively, the domain case-insensitively
+    /// in its A-label form.
+    pub fn verify_email(&self, email: &str) -> Result<()> {
+        let _span = debug_span!("verify_email", email).entered();
+
+        let Some(reference) = Mailbox::parse(email) else {
+            debug!("invalid email address");
+            return Err(Error::IdentityInvalid.into());
+        };
+
+        let matches = self
+            .get_subject_alt_name()
+            .into_iter()
+            .flat_map(|san| san.0.iter())
+            .filter_map(|name| match name {
+                GeneralName::Rfc822Name(email) => Some(email.as_str()),
+                GeneralName::OtherName(other) if other.type_id == SMTP_UTF8_MAILBOX => other
+                    .value
+                    .decode_as::<Utf8StringRef<'_>>()
+                    .ok()
+                    .map(|email| email.as_str()),
+                _ => None,
+            })
+            .filter_map(Mailbox::parse)
+            .any(|presented| presented == reference);
+
+        if !matches {
+            debug!("no matching email address");
+            return Err(Error::IdentityMismatch.into());
+        }
+
+        Ok(())
+    }
+
+    /// Verify that the certificate has a uniformResourceIdentifier
+    /// subject alternative name for the URI
+    ///
+    /// The scheme, authority and path are compared exactly,
+    /// the query and fragment are ignored.
+    pub fn verify_uri(&self, uri: &str) -> Result<()> {
+        let _span = debug_span!("verify_uri", uri).entered();
+
+        let Some(reference) = uri_components(uri) else {
+            debug!("invalid URI");
+            return Err(Error::IdentityInvalid.into());
+        };
+
+        let matches = self
+            .get_subject_alt_name()
+            .into_iter()
+            .flat_map(|san| san.0.iter())
+            .filter_map(|name| match name {
+                GeneralName::UniformResourceIdentifier(uri) => uri_components(uri.as_str()),
+                _ => None,
+            })
+            .any(|presented| presented == reference);
+
+        if !matches {
+            debug!("no matching URI");
+            return Err(Error::IdentityMismatch.into());
+        }
+
+        Ok(())
+    }
+
+    /// Common names of the subject with a string value
+    pub(crate) fn common_names(&self) -> Vec<&str> {
+        self.inner
+            .tbs_certificate
+            .subject
+            .0
+            .iter()
+            .flat_map(|rdn| rdn.0.iter())
+            .filter(|atv| atv.oid == CN)
+            .filter_map(|atv| {
+                atv.value
+                    .decode_as::<Utf8StringRef<'_>>()
+                    .map(|cn| cn.as_str())
+                    .or_else(|_| {
+                        atv.value
+                            .decode_as::<PrintableStringRef<'_>>()
+                            .map(|cn| cn.as_str())
+                    })
+                    .or_else(|_| {
+                        atv.value
+                            .decode_as::<Ia5StringRef<'_>>()
+                            .map(|cn| cn.as_str())
+                    })
+                    .ok()
+            })
+            .collect()
+    }
+}
+
+/// Match a presented DNS name against the reference host name in A-label form
+///
+/// A wildcard is only permitted as the complete left-most label and matches exactly one label,
+/// it has to be followed by at least two labels. Names with an invalid A-label never match.
+fn dns_name_matches(presented: &str, reference: &str) -> bool {
+    let presented = normalize_dns_name(presented);
+
+    if !presented.split('.').all(is_valid_label) {
+        debug!(presented, "invalid DNS name");
+        return false;
+    }
+
+    match presented.strip_prefix("*.") {
+        Some(suffix) => {
+            if suffix.split('.').count() < 2 || suffix.contains('*') {
+                return false;
+            }
+
+            reference
+                .split_once('.')
+                .is_some_and(|(label, rest)| !label.is_empty() && rest == suffix)
+        }
+        None => !presented.contains('*') && presented == reference,
+    }
+}
+
+/// Normalize a DNS name for matching: without one trailing dot of an absolute name
+/// and in lowercase
+///
+/// Host names and the names subject to name constraints have to be normalized alike,
+/// otherwise a name could match a host name but escape an excluded subtree.
+pub(crate) fn normalize_dns_name(name: &str) -> String {
+    name.strip_suffix('.').unwrap_or(name).to_ascii_lowercase()
+}
+
+/// Check a label of a presented DNS name,
+/// an A-label has to be the canonical encoding of a valid U-label
+fn is_valid_label(label: &str) -> bool {
+    if label.is_empty() {
+        return false;
+    }
+
+    if !label
+        .get(..4)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("xn--"))
+    {
+        return true;
+    }
+
+    let (unicode, result) = idna::domain_to_unicode(label);
+    result.is_ok()
+        && idna::domain_to_ascii_strict(&unicode)
+            .is_ok_and(|ascii| ascii.eq_ignore_ascii_case(label))
+}
+
+/// Email address with the domain in A-label form
+#[derive(Debug, PartialEq, Eq)]
+struct Mailbox<'a> {
+    local_part: &'a str,
+    domain: String,
+}
+
+impl<'a> Mailbox<'a> {
+    fn parse(email: &'a str) -> Option<Self> {
+        let (local_part, domain) = email.rsplit_once('@')?;
+        if local_part.is_empty() {
+            return None;
+        }
+
+        let domain = idna::domain_to_ascii_strict(domain).ok()?;
+        if domain.is_empty() {
+            return None;
+        }
+
+        Some(Self { local_part, domain })
+    }
+}
+
+/// Get the scheme, authority and path of an URI with an authority component
+fn uri_components(uri: &str) -> Option<(&str, &str, &str)> {
+    let (scheme, rest) = uri.split_once("://")?;
+    let (authority, rest) = rest.split_at(rest.find(['/', '?', '#']).unwrap_or(rest.len()));
+    let path = &rest[..rest.find(['?', '#']).unwrap_or(rest.len())];
+
+    if scheme.is_empty() || authority.is_empty() {
+        return None;
+    }
+
+    Some((scheme, authority, path))
+}
+
+fn ip_address(bytes: &[u8]) -> Option<IpAddr> {
+    match bytes.len() {
+        4 => <[u8; 4]>::try_from(bytes).ok().map(IpAddr::from),
+        16 => <[u8; 16]>::try_from(bytes).ok().map(IpAddr::from),
+        _ => None,
+    }
+}
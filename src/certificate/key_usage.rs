@@ -0,0 +1,88 @@
+use crate::Result;
+
+use super::{Certificate, Error};
+
+use std::collections::HashMap;
+
+use tracing::debug;
+use x509_cert::ext::pkix::{KeyUsage, KeyUsages};
+
+/// Intended purpose of the leaf key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyPurpose {
+    /// TLS with an ephemeral (EC)DH key exchange, the key signs the handshake
+    TlsEphemeralKeyExchange,
+    /// TLS with RSA key transport, the key encrypts the premaster secret
+    TlsRsaKeyTransport,
+    /// Signing of documents, the signer commits to the signed content
+    DocumentSigning,
+    /// Signing of certificate revocation lists
+    CrlSigning,
+}
+
+/// Key usages the leaf has to assert for each of its intended purposes
+///
+/// By default, the purposes require digitalSignature, keyEncipherment,
+/// nonRepudiation (contentCommitment) and cRLSign respectively.
+#[derive(Debug, Clone)]
+pub struct KeyUsagePolicy {
+    requirements: HashMap<KeyPurpose, KeyUsage>,
+}
+
+impl Default for KeyUsagePolicy {
+    fn default() -> Self {
+        let requirements = [
+            (
+                KeyPurpose::TlsEphemeralKeyExchange,
+                KeyUsages::DigitalSignature,
+            ),
+            (KeyPurpose::TlsRsaKeyTransport, KeyUsages::KeyEncipherment),
+            (KeyPurpose::DocumentSigning, KeyUsages::NonRepudiation),
+            (KeyPurpose::CrlSigning, KeyUsages::CRLSign),
+        ]
+        .into_iter()
+        .map(|(purpose, usage)| (purpose, KeyUsage(usage.into())))
+        .collect();
+
+        Self { requirements }
+    }
+}
+
+impl KeyUsagePolicy {
+    /// Set the key usages required for the purpose, all of them have to be asserted
+    pub fn set_requirement(mut self, purpose: KeyPurpose, key_usage: KeyUsage) -> Self {
+        self.requirements.insert(purpose, key_usage);
+        self
+    }
+
+    pub fn requirement(&self, purpose: KeyPurpose) -> Option<KeyUsage> {
+        self.requirements.get(&purpose).copied()
+    }
+
+    /// Check the key usage of the leaf against the requirements of its purposes
+    ///
+    /// A certificate without the key usage extension is valid for any usage.
+    pub(crate) fn check(&self, cert: &Certificate, purposes: &[KeyPurpose]) -> Result<()> {
+        let Some(ku) = cert.get_key_usage() else {
+            return Ok(());
+        };
+
+        for &purpose in purposes {
+            let Some(required) = self.requirement(purpose) else {
+                continue;
+            };
+
+            debug!(
+                purpose = ?purpose,
+                key_usage = ?ku.0,
+                required = ?required.0,
+                "checking key usage for purpose"
+            );
+            if !ku.0.contains(required.0) {
+                return Err(Error::KeyUsageViolation.into());
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,12 +1,20 @@
 pub mod extension;
+mod identity;
+pub mod key_usage;
+mod name_constraints;
+pub mod path_builder;
+mod policy;
+pub mod spiffe;
+pub mod trust_anchor;
+pub mod trust_store;
 pub mod validate;
 pub mod verify;
 
 use crate::Result;
 
-use self::extension::Extensions;
+use self::extension::{Extension, Extensions};
 
-use std::{fmt, slice};
+use std::{fmt, slice, time::Duration};
 
 use const_oid::ObjectIdentifier;
 use pkcs8::{
@@ -19,11 +27,11 @@ use pkcs8::der::{DecodePem, EncodePem};
 
 #[derive(Debug)]
 pub enum Error {
-    /// Certificate not valid yet
-    CertificateImmature,
+    /// Certificate not valid yet, with the time left until it becomes valid
+    CertificateImmature(Duration),
 
-    /// Certificate expired
-    CertificateExpired,
+    /// Certificate expired, with the time since it expired
+    CertificateExpired(Duration),
 
     /// Certificate is not valid
     CertificateInvalid,
@@ -48,13 +56,48 @@ pub enum Error {
 
     /// Unsupported extension
     UnsupportedExtension(ObjectIdentifier),
+
+    /// No trust anchor found for the certificate chain
+    TrustAnchorNotFound,
+
+    /// Trust anchor can't be used for path validation
+    TrustAnchorInvalid,
+
+    /// Name outside the permitted or within the excluded subtrees of the name constraints
+    NameConstraintsViolation,
+
+    /// No acceptable certificate policy although an explicit policy is required,
+    /// or an invalid policy mapping
+    PolicyViolation,
+
+    /// Extended key usage not permitted by the certificate
+    ExtendedKeyUsageViolation(ObjectIdentifier),
+
+    /// Certificate not valid for the host name, IP address or other identity
+    IdentityMismatch,
+
+    /// Identity to verify the certificate for is malformed
+    IdentityInvalid,
+
+    /// Missing or malformed SPIFFE ID
+    SpiffeIdInvalid,
+
+    /// Critical extensions which are neither supported nor handled by the caller
+    UnprocessedCriticalExtensions(Vec<ObjectIdentifier>),
+
+    /// Path building gave up after the maximum number of signature checks
+    PathBuildingLimitExceeded,
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::CertificateImmature => f.write_str("certificate not valid yet"),
-            Self::CertificateExpired => f.write_str("certificate expired"),
+            Self::CertificateImmature(remaining) => {
+                write!(f, "certificate not valid yet for {}s", remaining.as_secs())
+            }
+            Self::CertificateExpired(elapsed) => {
+                write!(f, "certificate expired {}s ago", elapsed.as_secs())
+            }
             Self::CertificateInvalid => f.write_str("certificate invalid"),
             Self::AlgorithmUnsupported => f.write_str("algorithm unsupported"),
             Self::AlgorithmMismatch => f.write_str("algorithm mismatch"),
@@ -67,6 +110,26 @@ impl fmt::Display for Error {
             Self::UnsupportedExtension(oid) => {
                 write!(f, "unsupported extension: {}", oid)
             }
+            Self::TrustAnchorNotFound => f.write_str("trust anchor not found"),
+            Self::TrustAnchorInvalid => f.write_str("trust anchor invalid"),
+            Self::NameConstraintsViolation => f.write_str("name constraints violation"),
+            Self::PolicyViolation => f.write_str("certificate policy violation"),
+            Self::ExtendedKeyUsageViolation(oid) => {
+                write!(f, "extended key usage not permitted: {}", oid)
+            }
+            Self::IdentityMismatch => f.write_str("identity mismatch"),
+            Self::IdentityInvalid => f.write_str("identity invalid"),
+            Self::SpiffeIdInvalid => f.write_str("SPIFFE ID invalid"),
+            Self::UnprocessedCriticalExtensions(oids) => {
+                f.write_str("unprocessed critical extensions:")?;
+                for oid in oids {
+                    write!(f, " {}", oid)?;
+                }
+                Ok(())
+            }
+            Self::PathBuildingLimitExceeded => {
+                f.write_str("path building signature check limit exceeded")
+            }
         }
     }
 }
@@ -77,6 +140,7 @@ impl std::error::Error for Error {}
 pub struct Certificate {
     pub(crate) inner: x509_cert::Certificate,
     pub(crate) parsed_extensions: Extensions,
+    pub(crate) unsupported_critical_extensions: Vec<ObjectIdentifier>,
 }
 
 impl Certificate {
@@ -144,6 +208,21 @@ impl Certificate {
     pub fn inner(&self) -> &x509_cert::Certificate {
         &self.inner
     }
+
+    /// Raw extensions of the certificate, including the unsupported ones
+    pub fn extensions(&self) -> &[x509_cert::ext::Extension] {
+        self.inner
+            .tbs_certificate
+            .extensions
+            .as_deref()
+            .unwrap_or_default()
+    }
+
+    /// OIDs of the critical extensions which are not supported or not processed
+    /// by the path validation, which fails unless they are handled by the caller
+    pub fn unsupported_critical_extensions(&self) -> &[ObjectIdentifier] {
+        &self.unsupported_critical_extensions
+    }
 }
 
 impl fmt::Debug for Certificate {
@@ -151,6 +230,10 @@ impl fmt::Debug for Certificate {
         f.debug_struct("Certificate")
             .field("inner", &self.inner)
             .field("extensions", &self.parsed_extensions)
+            .field(
+                "unsupported_critical_extensions",
+                &self.unsupported_critical_extensions,
+            )
             .finish()
     }
 }
@@ -159,22 +242,38 @@ impl TryFrom<x509_cert::Certificate> for Certificate {
     type Error = crate::error::Error;
 
     fn try_from(cert: x509_cert::Certificate) -> std::result::Result<Self, Self::Error> {
-        let ext = cert
+        let mut parsed_extensions = Vec::new();
+        let mut unsupported_critical_extensions = Vec::new();
+
+        for ext in cert
             .tbs_certificate
             .extensions
             .as_deref()
             .unwrap_or_default()
-            .iter()
-            .filter_map(|ext| match ext.try_into() {
-                Ok(v) => Some(Ok(v)),
-                Err(_) if !ext.critical => None,
-                Err(err) => Some(Err(err)),
-            })
-            .collect::<std::result::Result<_, _>>()?;
+        {
+            match Extension::try_from(ext) {
+                Ok(v) => {
+                    // Decoded but unprocessed critical extensions are left to the caller as well
+                    if ext.critical && !v.is_processed() {
+                        unsupported_critical_extensions.push(ext.extn_id);
+                    }
+                    parsed_extensions.push(v);
+                }
+                // Unsupported critical extensions are rejected by the path validation
+                Err(crate::error::Error::Certificate(Error::UnsupportedExtension(oid))) => {
+                    if ext.critical {
+                        unsupported_critical_extensions.push(oid);
+                    }
+                }
+                Err(_) if !ext.critical => {}
+                Err(err) => return Err(err),
+            }
+        }
 
         Ok(Self {
             inner: cert,
-            parsed_extensions: ext,
+            parsed_extensions,
+            unsupported_critical_extensions,
         })
     }
 }
@@ -0,0 +1,316 @@
+use crate::Result;
+
+use super::{identity::normalize_dns_name, Certificate, Error};
+
+use std::mem;
+
+use const_oid::db::rfc3280::EMAIL_ADDRESS;
+use pkcs8::der::asn1::{Any, Ia5StringRef, PrintableStringRef, Utf8StringRef};
+use tracing::debug;
+use x509_cert::{
+    attr::AttributeTypeAndValue,
+    ext::pkix::{name::GeneralName, NameConstraints},
+    name::Name,
+};
+
+/// Name constraints of the certificates processed so far as defined in
+/// [RFC 5280 Section 6.1.2](https://www.rfc-editor.org/rfc/rfc5280#section-6.1.2)
+///
+/// Instead of computing the intersection of the permitted subtrees,
+/// a name has to be permitted by every constraint extension which constrains its type.
+#[derive(Debug, Default)]
+pub(crate) struct NameConstraintsState<'a> {
+    constraints: Vec<&'a NameConstraints>,
+}
+
+/// Result of matching a name against a subtree
+#[derive(Debug, PartialEq, Eq)]
+enum Match {
+    /// The subtree is of a different name type
+    OtherType,
+    Within,
+    Outside,
+    /// The name type is not supported, so the name can't be matched
+    Unsupported,
+}
+
+/// Name of a certificate subject to name constraints
+enum SubjectName<'a> {
+    Directory(&'a Name),
+    Email(&'a str),
+    /// Common name of a leaf without DNS names, which may be matched as host name
+    CommonName(&'a str),
+    General(&'a GeneralName),
+}
+
+impl<'a> NameConstraintsState<'a> {
+    /// Add the name constraints of a CA certificate or trust anchor
+    pub fn add(&mut self, constraints: &'a NameConstraints) {
+        self.constraints.push(constraints);
+    }
+
+    /// Check the subject and subject alternative names of the certificate
+    /// against the permitted and excluded subtrees
+    ///
+    /// The common names of a leaf without DNS names are checked against the dNSName
+    /// constraints, as they may be used as host names by the common name fallback.
+    pub fn check(&self, cert: &Certificate, is_leaf: bool) -> Result<()> {
+        if self.constraints.is_empty() {
+            return Ok(());
+        }
+
+        let subject = &cert.inner.tbs_certificate.subject;
+
+        let mut names = Vec::new();
+        if !subject.0.is_empty() {
+            names.push(SubjectName::Directory(subject));
+        }
+
+        // Email addresses in the subject are subject to rfc822Name constraints
+        names.extend(
+            subject
+                .0
+                .iter()
+                .flat_map(|rdn| rdn.0.iter())
+                .filter(|atv| atv.oid == EMAIL_ADDRESS)
+                .filter_map(|atv| atv.value.decode_as::<Ia5StringRef<'_>>().ok())
+                .map(|email| SubjectName::Email(email.as_str())),
+        );
+
+        if let Some(san) = cert.get_subject_alt_name() {
+            names.extend(san.0.iter().map(SubjectName::General));
+        }
+
+        let has_dns_name = names
+            .iter()
+            .any(|name| matches!(name, SubjectName::General(GeneralName::DnsName(_))));
+        if is_leaf && !has_dns_name {
+            names.extend(
+                cert.common_names()
+                    .into_iter()
+                    .filter(|cn| is_host_name_like(cn))
+                    .map(SubjectName::CommonName),
+            );
+        }
+
+        for name in &names {
+            for constraints in &self.constraints {
+                check_name(name, constraints)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn check_name(name: &SubjectName<'_>, constraints: &NameConstraints) -> Result<()> {
+    // 1. The name must not be within any excluded subtree.
+    for subtree in constraints.excluded_subtrees.iter().flatten() {
+        match match_subtree(&subtree.base, name) {
+            Match::Within | Match::Unsupported => {
+                debug!(subtree = ?subtree.base, "name within excluded subtree");
+                return Err(Error::NameConstraintsViolation.into());
+            }
+            Match::OtherType | Match::Outside => {}
+        }
+    }
+
+    // 2. The name must be within a permitted subtree of its type, if there is any.
+    let mut constrained = false;
+    for subtree in constraints.permitted_subtrees.iter().flatten() {
+        match match_subtree(&subtree.base, name) {
+            Match::Within => return Ok(()),
+            Match::Outside | Match::Unsupported => constrained = true,
+            Match::OtherType => {}
+        }
+    }
+
+    if constrained {
+        debug!("name not within permitted subtrees");
+        return Err(Error::NameConstraintsViolation.into());
+    }
+
+    Ok(())
+}
+
+fn match_subtree(base: &GeneralName, name: &SubjectName<'_>) -> Match {
+    let matches = match (base, name) {
+        (GeneralName::DirectoryName(base), SubjectName::Directory(name)) => {
+            directory_name_matches(name, base)
+        }
+        (
+            GeneralName::DirectoryName(base),
+            SubjectName::General(GeneralName::DirectoryName(name)),
+        ) => directory_name_matches(name, base),
+        (GeneralName::Rfc822Name(base), SubjectName::Email(name)) => {
+            email_matches(name, base.as_str())
+        }
+        (GeneralName::Rfc822Name(base), SubjectName::General(GeneralName::Rfc822Name(name))) => {
+            email_matches(name.as_str(), base.as_str())
+        }
+        (GeneralName::DnsName(base), SubjectName::General(GeneralName::DnsName(name))) => {
+            dns_name_matches(name.as_str(), base.as_str())
+        }
+        (GeneralName::DnsName(base), SubjectName::CommonName(name)) => {
+            dns_name_matches(name, base.as_str())
+        }
+        (
+            GeneralName::UniformResourceIdentifier(base),
+            SubjectName::General(GeneralName::UniformResourceIdentifier(name)),
+        ) => uri_host(name.as_str()).is_some_and(|host| uri_host_matches(host, base.as_str())),
+        (GeneralName::IpAddress(base), SubjectName::General(GeneralName::IpAddress(name))) => {
+            ip_address_matches(name.as_bytes(), base.as_bytes())
+        }
+        (base, SubjectName::General(name))
+            if mem::discriminant(base) == mem::discriminant(*name) =>
+        {
+            return Match::Unsupported;
+        }
+        _ => return Match::OtherType,
+    };
+
+    if matches {
+        Match::Within
+    } else {
+        Match::Outside
+    }
+}
+
+/// A directory name is within the subtree if its first RDNs match the RDNs of the constraint
+fn directory_name_matches(name: &Name, constraint: &Name) -> bool {
+    name.0.len() >= constraint.0.len()
+        && name.0.iter().zip(&constraint.0).all(|(rdn, base)| {
+            rdn.0.len() == base.0.len()
+                && base
+                    .0
+                    .iter()
+                    .all(|base| rdn.0.iter().any(|atv| attribute_matches(atv, base)))
+        })
+}
+
+/// Attribute values are compared as defined in
+/// [RFC 5280 Section 7.1](https://www.rfc-editor.org/rfc/rfc5280#section-7.1):
+/// strings case-insensitively with insignificant whitespace folded, other values exactly
+fn attribute_matches(atv: &AttributeTypeAndValue, base: &AttributeTypeAndValue) -> bool {
+    if atv.oid != base.oid {
+        return false;
+    }
+
+    match (directory_string(&atv.value), directory_string(&base.value)) {
+        (Some(value), Some(base)) => fold_directory_string(value) == fold_directory_string(base),
+        _ => atv.value == base.value,
+    }
+}
+
+/// Get the value of a PrintableString or UTF8String attribute
+fn directory_string(value: &Any) -> Option<&str> {
+    value
+        .decode_as::<PrintableStringRef<'_>>()
+        .map(|value| value.as_str())
+        .or_else(|_| {
+            value
+                .decode_as::<Utf8StringRef<'_>>()
+                .map(|value| value.as_str())
+        })
+        .ok()
+}
+
+/// Remove leading and trailing whitespace, fold inner whitespace to one space
+/// and convert to lowercase
+fn fold_directory_string(value: &str) -> String {
+    value
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// A DNS name is within the subtree if it can be constructed by adding labels
+/// to the left of the constraint, a leading dot only permits subdomains.
+///
+/// The name is normalized like a host name to match, so an absolute name can't escape the subtree.
+fn dns_name_matches(name: &str, constraint: &str) -> bool {
+    if constraint.is_empty() {
+        return true;
+    }
+
+    let name = normalize_dns_name(name);
+    let constraint = constraint.to_ascii_lowercase();
+
+    if constraint.starts_with('.') {
+        return name.ends_with(&constraint);
+    }
+
+    name == constraint || name.ends_with(&format!(".{}", constraint))
+}
+
+/// A common name looks like a host name if it only consists of the characters of
+/// DNS names and wildcards, other common names never match a host name
+fn is_host_name_like(cn: &str) -> bool {
+    !cn.is_empty()
+        && cn
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'*'))
+}
+
+/// The host of an URI is within the subtree if it equals the constraint,
+/// only a leading dot in the constraint permits any subdomain instead
+fn uri_host_matches(host: &str, constraint: &str) -> bool {
+    let host = normalize_dns_name(host);
+    if constraint.starts_with('.') {
+        return host.ends_with(&constraint.to_ascii_lowercase());
+    }
+
+    host.eq_ignore_ascii_case(constraint)
+}
+
+/// An email address is within the subtree if it equals a mailbox constraint,
+/// is on the host of a host constraint or on a subdomain of a domain constraint
+fn email_matches(email: &str, constraint: &str) -> bool {
+    let Some((local, host)) = email.rsplit_once('@') else {
+        return false;
+    };
+
+    if let Some((constraint_local, constraint_host)) = constraint.rsplit_once('@') {
+        return local == constraint_local && host.eq_ignore_ascii_case(constraint_host);
+    }
+
+    if constraint.starts_with('.') {
+        return host
+            .to_ascii_lowercase()
+            .ends_with(&constraint.to_ascii_lowercase());
+    }
+
+    host.eq_ignore_ascii_case(constraint)
+}
+
+/// An IP address is within the subtree if it matches the address under the mask
+fn ip_address_matches(address: &[u8], constraint: &[u8]) -> bool {
+    if constraint.len() != address.len() * 2 {
+        return false;
+    }
+
+    let (network, mask) = constraint.split_at(address.len());
+    address
+        .iter()
+        .zip(network)
+        .zip(mask)
+        .all(|((address, network), mask)| address & mask == network & mask)
+}
+
+/// Get the host of an URI with an authority component
+fn uri_host(uri: &str) -> Option<&str> {
+    let (_, rest) = uri.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host_port = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host_port)| host_port);
+
+    // IP literals are not host names and never match a constraint
+    if host_port.starts_with('[') {
+        return None;
+    }
+
+    let host = host_port.split(':').next().unwrap_or_default();
+    (!host.is_empty()).then_some(host)
+}
@@ -0,0 +1,353 @@
+use crate::Result;
+
+use super::{
+    trust_anchor::TrustAnchor,
+    trust_store::TrustStore,
+    validate::{ValidatedPath, ValidationOptions},
+    Certificate, CertificateChain, Error,
+};
+
+use std::{
+    collections::{HashMap, HashSet},
+    ops::ControlFlow,
+};
+
+use pkcs8::der::Encode;
+
+use tracing::{debug, debug_span};
+
+/// Default maximum number of intermediates in a path
+const DEFAULT_MAX_INTERMEDIATES: usize = 8;
+
+/// Default maximum number of signature checks while building paths, as in Go's crypto/x509
+const DEFAULT_MAX_SIGNATURE_CHECKS: usize = 100;
+
+/// Builder for certificate paths from a leaf to a trust anchor
+///
+/// The intermediates may be given in any order and may contain certificates
+/// which are not part of the path or duplicates.
+#[derive(Debug, Clone)]
+pub struct PathBuilder<'a> {
+    trust_store: &'a TrustStore,
+    intermediates: Vec<Certificate>,
+    by_subject: HashMap<Vec<u8>, Vec<usize>>,
+    fingerprints: HashSet<Vec<u8>>,
+    options: ValidationOptions,
+    max_intermediates: usize,
+    max_signature_checks: usize,
+}
+
+/// Certificate path built and validated by a [`PathBuilder`]
+#[derive(Debug, Clone)]
+pub struct BuiltPath<'a> {
+    chain: CertificateChain,
+    validated: ValidatedPath<'a>,
+}
+
+impl<'a> BuiltPath<'a> {
+    /// Certificate chain from the first intermediate to the leaf
+    pub fn chain(&self) -> &CertificateChain {
+        &self.chain
+    }
+
+    /// Trust anchor the path was validated with
+    pub fn trust_anchor(&self) -> &'a TrustAnchor {
+        self.validated.trust_anchor()
+    }
+
+    pub fn into_chain(self) -> CertificateChain {
+        self.chain
+    }
+}
+
+impl<'a> PathBuilder<'a> {
+    pub fn new(trust_store: &'a TrustStore) -> Self {
+        Self {
+            trust_store,
+            intermediates: Vec::new(),
+            by_subject: HashMap::new(),
+            fingerprints: HashSet::new(),
+            options: ValidationOptions::default(),
+            max_intermediates: DEFAULT_MAX_INTERMEDIATES,
+            max_signature_checks: DEFAULT_MAX_SIGNATURE_CHECKS,
+        }
+    }
+
+    /// Add candidate intermediates, duplicates are ignored
+    pub fn add_intermediates(
+        mut self,
+        certs: impl IntoIterator<Item = impl Into<Certificate>>,
+    ) -> Result<Self> {
+        for cert in certs.into_iter().map(Into::into) {
+            if self.fingerprints.insert(cert.to_der()?) {
+                let subject = cert.inner.tbs_certificate.subject.to_der()?;
+                self.by_subject
+                    .entry(subject)
+                    .or_default()
+                    .push(self.intermediates.len());
+                self.intermediates.push(cert);
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Set the options for the validation of the candidate paths
+    pub fn set_options(mut self, options: ValidationOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Set the maximum number of intermediates in a path
+    pub fn set_max_intermediates(mut self, max: usize) -> Self {
+        self.max_intermediates = max;
+        self
+    }
+
+    /// Set the maximum number of signature checks while building paths, including the
+    /// signatures verified by the validation of candidate paths
+    ///
+    /// Building fails with [`Error::PathBuildingLimitExceeded`] once the limit is exceeded.
+    pub fn set_max_signature_checks(mut self, max: usize) -> Self {
+        self.max_signature_checks = max;
+        self
+    }
+
+    /// Build the shortest valid path for the leaf
+    pub fn build(&self, leaf: &Certificate) -> Result<BuiltPath<'a>> {
+        let mut found = None;
+        self.search(leaf, &mut |path| {
+            found = Some(path);
+            ControlFlow::Break(())
+        })?;
+
+        found.ok_or_else(|| Error::TrustAnchorNotFound.into())
+    }
+
+    /// Build all valid paths for the leaf, shorter paths first
+    pub fn build_all(&self, leaf: &Certificate) -> Result<Vec<BuiltPath<'a>>> {
+        let mut found = Vec::new();
+        self.search(leaf, &mut |path| {
+            found.push(path);
+            ControlFlow::Continue(())
+        })?;
+
+        Ok(found)
+    }
+
+    /// Search for valid paths by iterative deepening and pass them to the visitor
+    ///
+    /// Returns the error of the last rejected path if no path was found.
+    fn search(
+        &self,
+        leaf: &Certificate,
+        visit: &mut dyn FnMut(BuiltPath<'a>) -> ControlFlow<()>,
+    ) -> Result<()> {
+        let _span = debug_span!(
+            "build_path",
+            subject = %leaf.inner.tbs_certificate.subject,
+            candidates = self.intermediates.len(),
+        )
+        .entered();
+
+        let mut state = SearchState {
+            path: Vec::new(),
+            depth: 0,
+            reached: false,
+            last_error: None,
+            found: false,
+            signed: HashMap::new(),
+            signature_checks: 0,
+            limit_exceeded: false,
+        };
+
+        // Each round only completes paths with exactly `depth` intermediates,
+        // so the paths are found in the order of their length
+        for depth in 0..=self.max_intermediates {
+            state.depth = depth;
+            state.reached = false;
+
+            if self.extend(leaf, &mut state, visit).is_break() {
+                break;
+            }
+
+            // Without a partial path of this length there are no longer paths either
+            if !state.reached {
+                break;
+            }
+        }
+
+        if state.limit_exceeded {
+            return Err(Error::PathBuildingLimitExceeded.into());
+        }
+
+        match state.last_error {
+            Some(err) if !state.found => Err(err),
+            None if !state.found => Err(Error::TrustAnchorNotFound.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Try to complete the path with a trust anchor once it has the length of the round,
+    /// otherwise extend it with each candidate issuer of its topmost certificate
+    fn extend<'b>(
+        &'b self,
+        leaf: &Certificate,
+        state: &mut SearchState<'b>,
+        visit: &mut dyn FnMut(BuiltPath<'a>) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        let top = state.path.last().copied().unwrap_or(leaf);
+
+        // 1. Try to complete the path with each candidate trust anchor.
+        if state.path.len() == state.depth {
+            state.reached = true;
+
+            for trust_anchor in self.trust_store.find_issuers(top) {
+                let chain = CertificateChain::new(
+                    state.path.iter().rev().map(|&cert| cert.clone()).collect(),
+                    leaf.clone(),
+                );
+
+                // The validation verifies the signature of every certificate in the path
+                self.count_signature_checks(state, state.path.len() + 1)?;
+
+                debug!(
+                    trust_anchor = %trust_anchor.name(),
+                    path_length = state.path.len() + 1,
+                    "validating candidate path"
+                );
+                match chain.validate_path_with(trust_anchor, &self.options) {
+                    Ok(validated) => {
+                        state.found = true;
+                        visit(BuiltPath { chain, validated })?;
+                    }
+                    Err(err) => {
+                        debug!(error = %err, "candidate path rejected");
+                        state.last_error = Some(err);
+                    }
+                }
+            }
+
+            return ControlFlow::Continue(());
+        }
+
+        // 2. Extend the path with each candidate intermediate whose subject and key
+        //    are not already in the path, so re-issued certificates can't form a loop.
+        for issuer in self.find_issuers(top) {
+            if state
+                .path
+                .iter()
+                .copied()
+                .chain([leaf])
+                .any(|cert| same_subject_and_key(cert, issuer))
+            {
+                continue;
+            }
+
+            // Skip candidates which did not sign the topmost certificate,
+            // the result is kept for the later rounds
+            let key = (issuer as *const Certificate, top as *const Certificate);
+            let signed = match state.signed.get(&key) {
+                Some(&signed) => signed,
+                None => {
+                    self.count_signature_checks(state, 1)?;
+
+                    let signed = match issuer
+                        .verify_signature_with(top, self.options.signature_verifiers())
+                    {
+                        Ok(()) => true,
+                        Err(err) => {
+                            debug!(
+                                issuer = %issuer.inner.tbs_certificate.subject,
+                                error = %err,
+                                "candidate issuer rejected"
+                            );
+                            state.last_error = Some(err);
+                            false
+                        }
+                    };
+                    state.signed.insert(key, signed);
+                    signed
+                }
+            };
+            if !signed {
+                continue;
+            }
+
+            state.path.push(issuer);
+            let flow = self.extend(leaf, state, visit);
+            state.path.pop();
+            flow?;
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    /// Count signature checks against the limit, the search stops once it is exceeded
+    fn count_signature_checks(
+        &self,
+        state: &mut SearchState<'_>,
+        checks: usize,
+    ) -> ControlFlow<()> {
+        state.signature_checks += checks;
+        if state.signature_checks > self.max_signature_checks {
+            debug!(
+                limit = self.max_signature_checks,
+                "signature check limit exceeded"
+            );
+            state.limit_exceeded = true;
+            return ControlFlow::Break(());
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    /// Get the candidate intermediates which may have issued the given certificate
+    ///
+    /// Candidates are selected by the issuer name of the certificate and, if it has an
+    /// authority key identifier, by the subject key identifier of the candidates.
+    fn find_issuers(&self, cert: &Certificate) -> Vec<&Certificate> {
+        let key_id = cert
+            .get_authority_key_identifier()
+            .and_then(|aki| aki.key_identifier.as_ref());
+
+        cert.inner
+            .tbs_certificate
+            .issuer
+            .to_der()
+            .ok()
+            .and_then(|issuer| self.by_subject.get(&issuer))
+            .into_iter()
+            .flatten()
+            .map(|&index| &self.intermediates[index])
+            .filter(
+                |candidate| match (key_id, candidate.get_subject_key_identifier()) {
+                    (Some(key_id), Some(ski)) => key_id == &ski.0,
+                    _ => true,
+                },
+            )
+            .collect()
+    }
+}
+
+struct SearchState<'b> {
+    /// Intermediates from the issuer of the leaf upwards
+    path: Vec<&'b Certificate>,
+    /// Number of intermediates of the paths completed in the current round
+    depth: usize,
+    /// Whether a partial path reached the depth of the current round
+    reached: bool,
+    last_error: Option<crate::error::Error>,
+    found: bool,
+    /// Whether a candidate issuer signed a certificate, by the addresses of both
+    signed: HashMap<(*const Certificate, *const Certificate), bool>,
+    signature_checks: usize,
+    limit_exceeded: bool,
+}
+
+/// Certificates with the same subject and public key are the same entity,
+/// e.g. a re-issued certificate
+fn same_subject_and_key(a: &Certificate, b: &Certificate) -> bool {
+    let (a, b) = (&a.inner.tbs_certificate, &b.inner.tbs_certificate);
+    a.subject == b.subject && a.subject_public_key_info == b.subject_public_key_info
+}
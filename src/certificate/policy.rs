@@ -0,0 +1,492 @@
+use crate::Result;
+
+use super::{trust_anchor::TrustAnchor, validate::ValidationOptions, Certificate, Error};
+
+use std::{
+    collections::{BTreeSet, HashSet},
+    mem,
+};
+
+use const_oid::{db::rfc5280::ANY_POLICY, ObjectIdentifier};
+use tracing::debug;
+use x509_cert::{
+    anchor::CertPolicies,
+    ext::pkix::{CertificatePolicies, PolicyMappings},
+};
+
+/// Maximum number of nodes in the valid policy tree
+///
+/// Policy mappings can multiply the nodes with each certificate (CVE-2023-0464),
+/// larger trees fail the validation as in OpenSSL.
+const MAX_POLICY_NODES: usize = 1000;
+
+/// State of the certificate policy processing as defined in
+/// [RFC 5280 Section 6.1.2](https://www.rfc-editor.org/rfc/rfc5280#section-6.1.2)
+///
+/// Policy qualifiers are not tracked by the valid policy tree.
+#[derive(Debug)]
+pub(crate) struct PolicyState {
+    tree: Option<PolicyTree>,
+    /// Policies acceptable to the user, `None` if any policy is acceptable
+    user_initial_policy_set: Option<BTreeSet<ObjectIdentifier>>,
+    explicit_policy: usize,
+    inhibit_any_policy: usize,
+    policy_mapping: usize,
+}
+
+/// Valid policy tree, stored as one level of nodes per depth
+#[derive(Debug)]
+struct PolicyTree {
+    levels: Vec<Vec<PolicyNode>>,
+}
+
+#[derive(Debug)]
+struct PolicyNode {
+    valid_policy: ObjectIdentifier,
+    expected_policy_set: BTreeSet<ObjectIdentifier>,
+    /// Index of the parent node in the previous level
+    parent: usize,
+}
+
+impl PolicyNode {
+    fn new(valid_policy: ObjectIdentifier, parent: usize) -> Self {
+        Self {
+            valid_policy,
+            expected_policy_set: BTreeSet::from([valid_policy]),
+            parent,
+        }
+    }
+}
+
+impl PolicyState {
+    /// Initialize the state for a path of the given length
+    ///
+    /// The inputs of the options are combined with the policy constraints of the trust anchor.
+    pub fn new(path_len: usize, options: &ValidationOptions, trust_anchor: &TrustAnchor) -> Self {
+        // A set containing anyPolicy doesn't restrict the policies
+        let restricts = |set: &&BTreeSet<ObjectIdentifier>| !set.contains(&ANY_POLICY);
+        let user_initial_policy_set = match (
+            options.initial_policy_set().filter(restricts),
+            trust_anchor.policy_set().filter(restricts),
+        ) {
+            (Some(user), Some(anchor)) => Some(user.intersection(anchor).copied().collect()),
+            (user, anchor) => user.or(anchor).cloned(),
+        };
+
+        let flags = trust_anchor.policy_flags();
+        let initial = |set: bool| if set { 0 } else { path_len + 1 };
+
+        Self {
+            tree: Some(PolicyTree {
+                levels: vec![vec![PolicyNode::new(ANY_POLICY, 0)]],
+            }),
+            user_initial_policy_set,
+            explicit_policy: initial(
+                options.explicit_policy() || flags.contains(CertPolicies::RequireExplicitPolicy),
+            ),
+            inhibit_any_policy: initial(
+                options.inhibit_any_policy() || flags.contains(CertPolicies::InhibitAnyPolicy),
+            ),
+            policy_mapping: initial(
+                options.inhibit_policy_mapping()
+                    || flags.contains(CertPolicies::InhibitPolicyMapping),
+            ),
+        }
+    }
+
+    /// Process the certificate policies of a certificate
+    /// ([RFC 5280 Section 6.1.3](https://www.rfc-editor.org/rfc/rfc5280#section-6.1.3) (d) - (f))
+    pub fn process(&mut self, cert: &Certificate, is_final: bool) -> Result<()> {
+        let any_policy = self.inhibit_any_policy > 0 || (!is_final && is_self_issued(cert));
+
+        let valid = match (self.tree.as_mut(), cert.get_certificate_policies()) {
+            (Some(tree), Some(policies)) => tree.add_level(policies, any_policy)?,
+            (Some(_), None) => {
+                debug!("no certificate policies");
+                false
+            }
+            (None, _) => true,
+        };
+        if !valid {
+            self.tree = None;
+        }
+
+        if self.explicit_policy == 0 && self.tree.is_none() {
+            debug!("explicit policy required but no valid policy");
+            return Err(Error::PolicyViolation.into());
+        }
+
+        Ok(())
+    }
+
+    /// Process the policy mappings and constraints of an intermediate for the next certificate
+    /// ([RFC 5280 Section 6.1.4](https://www.rfc-editor.org/rfc/rfc5280#section-6.1.4) (a), (b), (h) - (j))
+    pub fn prepare_next(&mut self, cert: &Certificate) -> Result<()> {
+        if let Some(mappings) = cert.get_policy_mappings() {
+            if mappings.0.iter().any(|mapping| {
+                mapping.issuer_domain_policy == ANY_POLICY
+                    || mapping.subject_domain_policy == ANY_POLICY
+            }) {
+                debug!("policy mapped from or to anyPolicy");
+                return Err(Error::PolicyViolation.into());
+            }
+
+            let valid = match self.tree.as_mut() {
+                Some(tree) if self.policy_mapping > 0 => {
+                    tree.map_policies(mappings)?;
+                    true
+                }
+                Some(tree) => {
+                    debug!("policy mapping inhibited");
+                    tree.remove_mapped_policies(mappings)
+                }
+                None => true,
+            };
+            if !valid {
+                self.tree = None;
+            }
+        }
+
+        if !is_self_issued(cert) {
+            self.explicit_policy = self.explicit_policy.saturating_sub(1);
+            self.policy_mapping = self.policy_mapping.saturating_sub(1);
+            self.inhibit_any_policy = self.inhibit_any_policy.saturating_sub(1);
+        }
+
+        if let Some(pc) = cert.get_policy_constraints() {
+            if let Some(skip_certs) = pc.require_explicit_policy {
+                self.explicit_policy = self.explicit_policy.min(skip_certs as usize);
+            }
+            if let Some(skip_certs) = pc.inhibit_policy_mapping {
+                self.policy_mapping = self.policy_mapping.min(skip_certs as usize);
+            }
+        }
+
+        if let Some(iap) = cert.get_inhibit_any_policy() {
+            self.inhibit_any_policy = self.inhibit_any_policy.min(iap.0 as usize);
+        }
+
+        Ok(())
+    }
+
+    /// Finish the policy processing with the leaf
+    /// ([RFC 5280 Section 6.1.5](https://www.rfc-editor.org/rfc/rfc5280#section-6.1.5) (a), (b), (g))
+    ///
+    /// Returns the authorities-constrained and the user-constrained policy sets.
+    pub fn finish(
+        mut self,
+        leaf: &Certificate,
+    ) -> Result<(BTreeSet<ObjectIdentifier>, BTreeSet<ObjectIdentifier>)> {
+        self.explicit_policy = self.explicit_policy.saturating_sub(1);
+        if leaf
+            .get_policy_constraints()
+            .is_some_and(|pc| pc.require_explicit_policy == Some(0))
+        {
+            self.explicit_policy = 0;
+        }
+
+        let authorities_constrained = self
+            .tree
+            .as_ref()
+            .map(PolicyTree::policies)
+            .unwrap_or_default();
+
+        let valid = match (self.tree.as_mut(), &self.user_initial_policy_set) {
+            (Some(tree), Some(user)) => tree.intersect(user),
+            (tree, _) => tree.is_some(),
+        };
+        if !valid {
+            self.tree = None;
+        }
+
+        if self.explicit_policy == 0 && self.tree.is_none() {
+            debug!("explicit policy required but no acceptable policy");
+            return Err(Error::PolicyViolation.into());
+        }
+
+        let user_constrained = self
+            .tree
+            .as_ref()
+            .map(PolicyTree::policies)
+            .unwrap_or_default();
+
+        Ok((authorities_constrained, user_constrained))
+    }
+}
+
+impl PolicyTree {
+    /// Add the nodes for the policies of the next certificate
+    ///
+    /// Returns `false` if the tree became empty.
+    fn add_level(&mut self, policies: &CertificatePolicies, any_policy: bool) -> Result<bool> {
+        let parents = self.levels.last().expect("tree has a root");
+        let mut level: Vec<PolicyNode> = Vec::new();
+
+        // 1. Add each policy below the nodes expecting it, or below anyPolicy nodes otherwise.
+        for policy in policies
+            .0
+            .iter()
+            .map(|info| info.policy_identifier)
+            .filter(|&policy| policy != ANY_POLICY)
+        {
+            let expecting: Vec<_> = parents
+                .iter()
+                .enumerate()
+                .filter(|(_, parent)| parent.expected_policy_set.contains(&policy))
+                .map(|(index, _)| index)
+                .collect();
+
+            if expecting.is_empty() {
+                level.extend(
+                    parents
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, parent)| parent.valid_policy == ANY_POLICY)
+                        .map(|(index, _)| PolicyNode::new(policy, index)),
+                );
+            } else {
+                level.extend(
+                    expecting
+                        .into_iter()
+                        .map(|index| PolicyNode::new(policy, index)),
+                );
+            }
+
+            self.check_size(level.len())?;
+        }
+
+        // 2. If anyPolicy is asserted and not inhibited,
+        //    add each expected policy not matched yet below its node.
+        let asserts_any_policy = policies
+            .0
+            .iter()
+            .any(|info| info.policy_identifier == ANY_POLICY);
+        if asserts_any_policy && any_policy {
+            for (index, parent) in parents.iter().enumerate() {
+                for &policy in &parent.expected_policy_set {
+                    if !level
+                        .iter()
+                        .any(|node| node.parent == index && node.valid_policy == policy)
+                    {
+                        level.push(PolicyNode::new(policy, index));
+                    }
+                }
+
+                self.check_size(level.len())?;
+            }
+        }
+
+        self.levels.push(level);
+
+        // 3. Remove the branches which did not reach the new level.
+        Ok(self.prune())
+    }
+
+    /// Set the expected policies of the mapped nodes in the last level
+    fn map_policies(&mut self, mappings: &PolicyMappings) -> Result<()> {
+        let level = self.levels.last_mut().expect("tree has a root");
+
+        let issuer_policies: BTreeSet<_> = mappings
+            .0
+            .iter()
+            .map(|mapping| mapping.issuer_domain_policy)
+            .collect();
+
+        for issuer_policy in issuer_policies {
+            let subject_policies: BTreeSet<_> = mappings
+                .0
+                .iter()
+                .filter(|mapping| mapping.issuer_domain_policy == issuer_policy)
+                .map(|mapping| mapping.subject_domain_policy)
+                .collect();
+
+            let mut mapped = false;
+            for node in level
+                .iter_mut()
+                .filter(|node| node.valid_policy == issuer_policy)
+            {
+                node.expected_policy_set = subject_policies.clone();
+                mapped = true;
+            }
+
+            // Without a node for the issuer domain policy, a sibling of an anyPolicy node is mapped
+            if !mapped {
+                if let Some(parent) = level
+                    .iter()
+                    .find(|node| node.valid_policy == ANY_POLICY)
+                    .map(|node| node.parent)
+                {
+                    level.push(PolicyNode {
+                        valid_policy: issuer_policy,
+                        expected_policy_set: subject_policies,
+                        parent,
+                    });
+                }
+            }
+        }
+
+        // At most one node is added for each mapping
+        self.check_size(0)
+    }
+
+    /// Remove the nodes of the mapped policies in the last level
+    ///
+    /// Returns `false` if the tree became empty.
+    fn remove_mapped_policies(&mut self, mappings: &PolicyMappings) -> bool {
+        let depth = self.levels.len() - 1;
+        self.retain(depth, |_, node| {
+            !mappings
+                .0
+                .iter()
+                .any(|mapping| mapping.issuer_domain_policy == node.valid_policy)
+        });
+
+        self.prune()
+    }
+
+    /// Intersect the tree with the policies acceptable to the user
+    ///
+    /// Returns `false` if the tree became empty.
+    fn intersect(&mut self, user_policies: &BTreeSet<ObjectIdentifier>) -> bool {
+        let depth = self.levels.len() - 1;
+
+        // 1. Policies of the nodes below anyPolicy nodes, which are in the domain of the user.
+        let mut node_set_policies = BTreeSet::new();
+        for d in 1..=depth {
+            node_set_policies.extend(
+                self.levels[d]
+                    .iter()
+                    .filter(|node| self.parent(d, node).valid_policy == ANY_POLICY)
+                    .map(|node| node.valid_policy),
+            );
+        }
+
+        // 2. Remove these nodes and their descendants if the policy is not acceptable.
+        for d in 1..=depth {
+            let invalid: Vec<_> = self.levels[d]
+                .iter()
+                .map(|node| {
+                    self.parent(d, node).valid_policy == ANY_POLICY
+                        && node.valid_policy != ANY_POLICY
+                        && !user_policies.contains(&node.valid_policy)
+                })
+                .collect();
+            self.retain(d, |index, _| !invalid[index]);
+        }
+
+        // 3. Replace an anyPolicy leaf with the acceptable policies not in the tree yet.
+        if let Some(any) = self.levels[depth]
+            .iter()
+            .position(|node| node.valid_policy == ANY_POLICY)
+        {
+            let parent = self.levels[depth][any].parent;
+            self.levels[depth].extend(
+                user_policies
+                    .difference(&node_set_policies)
+                    .map(|&policy| PolicyNode::new(policy, parent)),
+            );
+            self.retain(depth, |index, _| index != any);
+        }
+
+        // 4. Remove the branches which no longer reach the leaf level.
+        self.prune()
+    }
+
+    /// Policies of the leaf level in the domain of the trust anchor
+    ///
+    /// Each leaf node is traced back to the topmost node of its branch
+    /// which is not anyPolicy, i.e. before any policy mapping.
+    fn policies(&self) -> BTreeSet<ObjectIdentifier> {
+        let depth = self.levels.len() - 1;
+
+        self.levels[depth]
+            .iter()
+            .map(|mut node| {
+                let mut d = depth;
+                while d > 1 && self.parent(d, node).valid_policy != ANY_POLICY {
+                    node = self.parent(d, node);
+                    d -= 1;
+                }
+                node.valid_policy
+            })
+            .collect()
+    }
+
+    /// Check that the tree with the given number of new nodes stays within the maximum size
+    fn check_size(&self, new_nodes: usize) -> Result<()> {
+        let nodes = self.levels.iter().map(Vec::len).sum::<usize>() + new_nodes;
+        if nodes > MAX_POLICY_NODES {
+            debug!(nodes, "valid policy tree too large");
+            return Err(Error::PolicyViolation.into());
+        }
+
+        Ok(())
+    }
+
+    fn parent(&self, depth: usize, node: &PolicyNode) -> &PolicyNode {
+        &self.levels[depth - 1][node.parent]
+    }
+
+    /// Remove the nodes without children above the leaf level
+    ///
+    /// Returns `false` if the root was removed.
+    fn prune(&mut self) -> bool {
+        for depth in (0..self.levels.len() - 1).rev() {
+            let parents: HashSet<_> = self.levels[depth + 1]
+                .iter()
+                .map(|node| node.parent)
+                .collect();
+            self.retain(depth, |index, _| parents.contains(&index));
+        }
+
+        !self.levels[0].is_empty()
+    }
+
+    /// Retain the nodes of a level, removing the descendants of the other nodes
+    fn retain(&mut self, depth: usize, keep: impl Fn(usize, &PolicyNode) -> bool) {
+        let mut removed: Vec<bool> = self.levels[depth]
+            .iter()
+            .enumerate()
+            .map(|(index, node)| !keep(index, node))
+            .collect();
+
+        for d in depth..self.levels.len() {
+            // New index of each node of the level, if retained
+            let mut indices = Vec::with_capacity(removed.len());
+            let mut next_index = 0;
+            for &removed in &removed {
+                if removed {
+                    indices.push(None);
+                } else {
+                    indices.push(Some(next_index));
+                    next_index += 1;
+                }
+            }
+
+            let level = mem::take(&mut self.levels[d]);
+            self.levels[d] = level
+                .into_iter()
+                .zip(&removed)
+                .filter(|(_, &removed)| !removed)
+                .map(|(node, _)| node)
+                .collect();
+
+            if let Some(children) = self.levels.get_mut(d + 1) {
+                removed = children
+                    .iter_mut()
+                    .map(|child| match indices[child.parent] {
+                        Some(parent) => {
+                            child.parent = parent;
+                            false
+                        }
+                        None => true,
+                    })
+                    .collect();
+            }
+        }
+    }
+}
+
+fn is_self_issued(cert: &Certificate) -> bool {
+    cert.inner.tbs_certificate.subject == cert.inner.tbs_certificate.issuer
+}
@@ -0,0 +1,208 @@
+use crate::Result;
+
+use super::{
+    trust_store::TrustStore,
+    validate::{ValidatedPath, ValidationOptions},
+    CertificateChain, Error,
+};
+
+use std::{collections::HashMap, fmt};
+
+use tracing::{debug, debug_span};
+use x509_cert::ext::pkix::{name::GeneralName, KeyUsages};
+
+/// Maximum length of a SPIFFE ID in bytes
+const MAX_SPIFFE_ID_LENGTH: usize = 2048;
+
+/// SPIFFE ID as defined in the [SPIFFE ID specification](https://github.com/spiffe/spiffe/blob/main/standards/SPIFFE-ID.md)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpiffeId {
+    trust_domain: String,
+    path: String,
+}
+
+impl SpiffeId {
+    /// Parse a SPIFFE ID of the form `spiffe://<trust domain>/<path>`
+    pub fn parse(id: &str) -> Result<Self> {
+        if id.len() > MAX_SPIFFE_ID_LENGTH {
+            return Err(Error::SpiffeIdInvalid.into());
+        }
+
+        let rest = id.strip_prefix("spiffe://").ok_or(Error::SpiffeIdInvalid)?;
+        let (trust_domain, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+
+        if !is_valid_trust_domain(trust_domain) {
+            return Err(Error::SpiffeIdInvalid.into());
+        }
+
+        // The path is either empty or consists of non-empty segments,
+        // which must not be relative path modifiers
+        let valid_path = path.is_empty()
+            || path[1..].split('/').all(|segment| {
+                !segment.is_empty()
+                    && segment != "."
+                    && segment != ".."
+                    && segment
+                        .bytes()
+                        .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'_'))
+            });
+        if !valid_path {
+            return Err(Error::SpiffeIdInvalid.into());
+        }
+
+        Ok(Self {
+            trust_domain: trust_domain.to_owned(),
+            path: path.to_owned(),
+        })
+    }
+
+    pub fn trust_domain(&self) -> &str {
+        &self.trust_domain
+    }
+
+    /// Path of the workload, empty or starting with a slash
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl fmt::Display for SpiffeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "spiffe://{}{}", self.trust_domain, self.path)
+    }
+}
+
+/// Trust bundles of SPIFFE trust domains, each with its own trust store
+#[derive(Debug, Clone, Default)]
+pub struct TrustBundleSet {
+    bundles: HashMap<String, TrustStore>,
+}
+
+impl TrustBundleSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the trust bundle of a trust domain, replacing any previous bundle
+    pub fn add(&mut self, trust_domain: &str, bundle: TrustStore) -> Result<()> {
+        if !is_valid_trust_domain(trust_domain) {
+            return Err(Error::SpiffeIdInvalid.into());
+        }
+
+        self.bundles.insert(trust_domain.to_owned(), bundle);
+
+        Ok(())
+    }
+
+    /// Get the trust bundle of a trust domain
+    pub fn get(&self, trust_domain: &str) -> Option<&TrustStore> {
+        self.bundles.get(trust_domain)
+    }
+}
+
+/// Result of a successful X.509-SVID validation
+#[derive(Debug, Clone)]
+pub struct ValidatedSvid<'a> {
+    spiffe_id: SpiffeId,
+    validated_path: ValidatedPath<'a>,
+}
+
+impl<'a> ValidatedSvid<'a> {
+    /// SPIFFE ID of the leaf
+    pub fn spiffe_id(&self) -> &SpiffeId {
+        &self.spiffe_id
+    }
+
+    pub fn validated_path(&self) -> &ValidatedPath<'a> {
+        &self.validated_path
+    }
+}
+
+impl CertificateChain {
+    /// Validate the chain as X.509-SVID as defined in the
+    /// [X.509-SVID specification](https://github.com/spiffe/spiffe/blob/main/standards/X509-SVID.md)
+    ///
+    /// In addition to the path validation with the trust bundle of the trust domain of the leaf,
+    /// the leaf must have exactly one URI subject alternative name with a SPIFFE ID,
+    /// must not be a CA and must only be usable for signatures. The signing certificates
+    /// must be CA certificates with the keyCertSign key usage.
+    pub fn validate_svid<'a>(
+        &self,
+        bundles: &'a TrustBundleSet,
+        options: &ValidationOptions,
+    ) -> Result<ValidatedSvid<'a>> {
+        let _span = debug_span!("validate_svid").entered();
+
+        // 1. Get the SPIFFE ID of the leaf.
+        let mut uris = self
+            .leaf
+            .get_subject_alt_name()
+            .into_iter()
+            .flat_map(|san| san.0.iter())
+            .filter_map(|name| match name {
+                GeneralName::UniformResourceIdentifier(uri) => Some(uri.as_str()),
+                _ => None,
+            });
+
+        let spiffe_id = match (uris.next(), uris.next()) {
+            (Some(uri), None) => SpiffeId::parse(uri)?,
+            _ => {
+                debug!("leaf must have exactly one URI subject alternative name");
+                return Err(Error::SpiffeIdInvalid.into());
+            }
+        };
+        debug!(spiffe_id = %spiffe_id, "checking X.509-SVID");
+
+        // 2. Check the basic constraints and key usage of the leaf.
+        if self.leaf.get_basic_constraints().is_some_and(|bc| bc.ca) {
+            debug!("leaf must not be a CA certificate");
+            return Err(Error::BasicConstraintsViolation.into());
+        }
+
+        let ku = self.leaf.get_key_usage().ok_or(Error::KeyUsageViolation)?;
+        if !ku.0.contains(KeyUsages::DigitalSignature)
+            || ku.0.contains(KeyUsages::KeyCertSign)
+            || ku.0.contains(KeyUsages::CRLSign)
+        {
+            debug!(key_usage = ?ku.0, "invalid key usage for leaf");
+            return Err(Error::KeyUsageViolation.into());
+        }
+
+        // 3. Check the basic constraints and key usage of the signing certificates.
+        for cert in &self.intermediates {
+            if !cert.get_basic_constraints().is_some_and(|bc| bc.ca) {
+                debug!("signing certificate must be a CA certificate");
+                return Err(Error::BasicConstraintsViolation.into());
+            }
+
+            if !cert
+                .get_key_usage()
+                .is_some_and(|ku| ku.0.contains(KeyUsages::KeyCertSign))
+            {
+                debug!("signing certificate must have the keyCertSign key usage");
+                return Err(Error::KeyUsageViolation.into());
+            }
+        }
+
+        // 4. Validate the path with the trust bundle of the trust domain.
+        let bundle = bundles.get(spiffe_id.trust_domain()).ok_or_else(|| {
+            debug!("no trust bundle for trust domain");
+            Error::TrustAnchorNotFound
+        })?;
+
+        let validated_path = self.validate_path_in(bundle, options)?;
+
+        Ok(ValidatedSvid {
+            spiffe_id,
+            validated_path,
+        })
+    }
+}
+
+/// A trust domain consists of lowercase letters, digits, dots, dashes and underscores
+fn is_valid_trust_domain(trust_domain: &str) -> bool {
+    !trust_domain.is_empty()
+        && trust_domain.bytes().all(|b| {
+            b.is_ascii_lowercase() || b.is_ascii_digit() || matches!(b, b'.' | b'-' | b'_')
+        })
+}
@@ -0,0 +1,245 @@
+use crate::Result;
+
+use super::{extension::Extension, Certificate, Error};
+
+use std::collections::BTreeSet;
+
+use const_oid::ObjectIdentifier;
+use pkcs8::{der::Decode, spki::SubjectPublicKeyInfoOwned};
+use x509_cert::{
+    anchor::{CertPolicies, CertPolicyFlags, TrustAnchorChoice, TrustAnchorInfo},
+    ext::pkix::{CertificatePolicies, NameConstraints},
+    name::Name,
+    serial_number::SerialNumber,
+    TbsCertificate,
+};
+
+/// Trust anchor as defined in [RFC 5280 Section 6.1.1](https://www.rfc-editor.org/rfc/rfc5280#section-6.1.1)
+///
+/// A trust anchor consists of a name and a public key and may carry constraints
+/// for the paths it validates. Only these constraints are applied to the anchor,
+/// the validity period and key usage of an anchor certificate are not checked.
+#[derive(Debug, Clone)]
+pub struct TrustAnchor {
+    name: Name,
+    public_key: SubjectPublicKeyInfoOwned,
+    key_identifier: Option<Vec<u8>>,
+    path_len_constraint: Option<u8>,
+    name_constraints: Option<NameConstraints>,
+    policy_set: Option<BTreeSet<ObjectIdentifier>>,
+    policy_flags: CertPolicyFlags,
+    certificate: Option<Certificate>,
+}
+
+impl TrustAnchor {
+    /// Create a trust anchor from a bare public key and name without any constraints
+    pub fn from_public_key(name: Name, public_key: SubjectPublicKeyInfoOwned) -> Self {
+        Self {
+            name,
+            public_key,
+            key_identifier: None,
+            path_len_constraint: None,
+            name_constraints: None,
+            policy_set: None,
+            policy_flags: CertPolicyFlags::default(),
+            certificate: None,
+        }
+    }
+
+    /// Create a trust anchor from a DER encoded `TrustAnchorChoice` defined in
+    /// [RFC 5914](https://www.rfc-editor.org/rfc/rfc5914), which also accepts a bare certificate
+    pub fn from_der(bytes: impl AsRef<[u8]>) -> Result<Self> {
+        TrustAnchorChoice::from_der(bytes.as_ref())?.try_into()
+    }
+
+    /// Set the key identifier matched against the authority key identifier of issued certificates
+    pub fn set_key_identifier(mut self, key_identifier: impl Into<Vec<u8>>) -> Self {
+        self.key_identifier = Some(key_identifier.into());
+        self
+    }
+
+    /// Set the maximum number of intermediates below the trust anchor
+    pub fn set_path_len_constraint(mut self, path_len_constraint: u8) -> Self {
+        self.path_len_constraint = Some(path_len_constraint);
+        self
+    }
+
+    /// Set the name constraints for the certificates below the trust anchor
+    pub fn set_name_constraints(mut self, name_constraints: NameConstraints) -> Self {
+        self.name_constraints = Some(name_constraints);
+        self
+    }
+
+    /// Set the certificate policies acceptable for the paths below the trust anchor,
+    /// which restrict the initial policy set of the validation
+    pub fn set_policy_set(mut self, policies: impl IntoIterator<Item = ObjectIdentifier>) -> Self {
+        self.policy_set = Some(policies.into_iter().collect());
+        self
+    }
+
+    /// Set the policy flags which are combined with the policy inputs of the validation
+    pub fn set_policy_flags(mut self, flags: impl Into<CertPolicyFlags>) -> Self {
+        self.policy_flags = flags.into();
+        self
+    }
+
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    pub fn public_key(&self) -> &SubjectPublicKeyInfoOwned {
+        &self.public_key
+    }
+
+    pub fn key_identifier(&self) -> Option<&[u8]> {
+        self.key_identifier.as_deref()
+    }
+
+    pub fn path_len_constraint(&self) -> Option<u8> {
+        self.path_len_constraint
+    }
+
+    pub fn name_constraints(&self) -> Option<&NameConstraints> {
+        self.name_constraints.as_ref()
+    }
+
+    pub fn policy_set(&self) -> Option<&BTreeSet<ObjectIdentifier>> {
+        self.policy_set.as_ref()
+    }
+
+    pub fn policy_flags(&self) -> CertPolicyFlags {
+        self.policy_flags
+    }
+
+    /// Certificate the trust anchor was created from, if any
+    pub fn certificate(&self) -> Option<&Certificate> {
+        self.certificate.as_ref()
+    }
+
+    pub(crate) fn serial_number(&self) -> Option<&SerialNumber> {
+        self.certificate
+            .as_ref()
+            .map(|cert| &cert.inner.tbs_certificate.serial_number)
+    }
+
+    /// Apply the constraints of an extension of the trust anchor certificate
+    /// as described in [RFC 5937 Section 3](https://www.rfc-editor.org/rfc/rfc5937#section-3)
+    fn apply_extension(&mut self, ext: &Extension) {
+        match ext {
+            Extension::SubjectKeyIdentifier(ski) => {
+                self.key_identifier = Some(ski.0.as_bytes().to_vec());
+            }
+            Extension::BasicConstraints(bc) => {
+                self.path_len_constraint = bc.path_len_constraint;
+            }
+            Extension::NameConstraints(nc) => {
+                self.name_constraints = Some(nc.clone());
+            }
+            Extension::CertificatePolicies(cp) => {
+                self.policy_set = Some(policy_identifiers(cp));
+            }
+            Extension::PolicyConstraints(pc) => {
+                if pc.require_explicit_policy == Some(0) {
+                    self.policy_flags |= CertPolicies::RequireExplicitPolicy;
+                }
+                if pc.inhibit_policy_mapping == Some(0) {
+                    self.policy_flags |= CertPolicies::InhibitPolicyMapping;
+                }
+            }
+            Extension::InhibitAnyPolicy(iap) if iap.0 == 0 => {
+                self.policy_flags |= CertPolicies::InhibitAnyPolicy;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn policy_identifiers(policies: &CertificatePolicies) -> BTreeSet<ObjectIdentifier> {
+    policies
+        .0
+        .iter()
+        .map(|info| info.policy_identifier)
+        .collect()
+}
+
+impl From<Certificate> for TrustAnchor {
+    fn from(cert: Certificate) -> Self {
+        let tbs = &cert.inner.tbs_certificate;
+
+        let mut anchor =
+            Self::from_public_key(tbs.subject.clone(), tbs.subject_public_key_info.clone());
+        for ext in &cert.parsed_extensions {
+            anchor.apply_extension(ext);
+        }
+        anchor.certificate = Some(cert);
+
+        anchor
+    }
+}
+
+impl From<&Certificate> for TrustAnchor {
+    fn from(cert: &Certificate) -> Self {
+        cert.clone().into()
+    }
+}
+
+impl TryFrom<TrustAnchorInfo> for TrustAnchor {
+    type Error = crate::error::Error;
+
+    fn try_from(info: TrustAnchorInfo) -> std::result::Result<Self, Self::Error> {
+        // Without certification path controls, the trust anchor has no name
+        // and can't be used for path validation (RFC 5914 Section 2)
+        let controls = info.cert_path.ok_or(Error::TrustAnchorInvalid)?;
+
+        let certificate = controls
+            .certificate
+            .map(Certificate::try_from)
+            .transpose()?;
+
+        Ok(Self {
+            name: controls.ta_name,
+            public_key: info.pub_key,
+            key_identifier: Some(info.key_id.as_bytes().to_vec()),
+            path_len_constraint: controls
+                .path_len_constraint
+                .map(|len| u8::try_from(len).unwrap_or(u8::MAX)),
+            name_constraints: controls.name_constr,
+            policy_set: controls.policy_set.as_ref().map(policy_identifiers),
+            policy_flags: controls.policy_flags.unwrap_or_default(),
+            certificate,
+        })
+    }
+}
+
+impl TryFrom<TbsCertificate> for TrustAnchor {
+    type Error = crate::error::Error;
+
+    fn try_from(tbs: TbsCertificate) -> std::result::Result<Self, Self::Error> {
+        let mut anchor = Self::from_public_key(tbs.subject, tbs.subject_public_key_info);
+
+        // Unsupported extensions carry no constraints for the trust anchor and are ignored,
+        // as for a trust anchor certificate
+        for ext in tbs.extensions.as_deref().unwrap_or_default() {
+            match Extension::try_from(ext) {
+                Ok(ext) => anchor.apply_extension(&ext),
+                Err(crate::error::Error::Certificate(Error::UnsupportedExtension(_))) => {}
+                Err(_) if !ext.critical => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(anchor)
+    }
+}
+
+impl TryFrom<TrustAnchorChoice> for TrustAnchor {
+    type Error = crate::error::Error;
+
+    fn try_from(choice: TrustAnchorChoice) -> std::result::Result<Self, Self::Error> {
+        match choice {
+            TrustAnchorChoice::Certificate(cert) => Ok(Certificate::try_from(cert)?.into()),
+            TrustAnchorChoice::TbsCertificate(tbs) => tbs.try_into(),
+            TrustAnchorChoice::TaInfo(info) => info.try_into(),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::Result;
+
+use super::{trust_anchor::TrustAnchor, Certificate};
+
+use std::collections::HashMap;
+
+use pkcs8::der::Encode;
+use x509_cert::name::Name;
+
+/// Collection of trust anchors indexed by subject name and subject key identifier
+#[derive(Debug, Clone, Default)]
+pub struct TrustStore {
+    anchors: Vec<TrustAnchor>,
+    by_subject: HashMap<Vec<u8>, Vec<usize>>,
+    by_key_identifier: HashMap<Vec<u8>, Vec<usize>>,
+}
+
+impl TrustStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a trust store with the given trust anchors
+    pub fn from_anchors(anchors: impl IntoIterator<Item = impl Into<TrustAnchor>>) -> Result<Self> {
+        let mut store = Self::new();
+        for anchor in anchors {
+            store.add(anchor)?;
+        }
+
+        Ok(store)
+    }
+
+    /// Create a trust store with the given trust anchor certificates
+    pub fn from_certificates(anchors: impl IntoIterator<Item = Certificate>) -> Result<Self> {
+        Self::from_anchors(anchors)
+    }
+
+    /// Add a trust anchor
+    pub fn add(&mut self, anchor: impl Into<TrustAnchor>) -> Result<()> {
+        let anchor = anchor.into();
+        let index = self.anchors.len();
+
+        let subject = anchor.name().to_der()?;
+        self.by_subject.entry(subject).or_default().push(index);
+
+        if let Some(key_id) = anchor.key_identifier() {
+            self.by_key_identifier
+                .entry(key_id.to_vec())
+                .or_default()
+                .push(index);
+        }
+
+        self.anchors.push(anchor);
+
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.anchors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.anchors.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &TrustAnchor> {
+        self.anchors.iter()
+    }
+
+    /// Get the trust anchors with the given name
+    pub fn find_by_subject(&self, subject: &Name) -> impl Iterator<Item = &TrustAnchor> {
+        let indices = subject
+            .to_der()
+            .ok()
+            .and_then(|subject| self.by_subject.get(&subject));
+
+        self.resolve(indices)
+    }
+
+    /// Get the trust anchors with the given key identifier
+    pub fn find_by_key_identifier(&self, key_id: &[u8]) -> impl Iterator<Item = &TrustAnchor> {
+        self.resolve(self.by_key_identifier.get(key_id))
+    }
+
+    /// Get the trust anchors which may have issued the given certificate
+    ///
+    /// Candidates are selected by the issuer name of the certificate. If the certificate has an
+    /// authority key identifier, anchors with a different key identifier are skipped and
+    /// anchors with a matching one are returned first.
+    pub fn find_issuers(&self, cert: &Certificate) -> Vec<&TrustAnchor> {
+        let issuer = &cert.inner.tbs_certificate.issuer;
+        let mut candidates: Vec<_> = self.find_by_subject(issuer).collect();
+
+        let key_id = cert
+            .get_authority_key_identifier()
+            .and_then(|aki| aki.key_identifier.as_ref());
+
+        if let Some(key_id) = key_id {
+            candidates.retain(|anchor| {
+                anchor
+                    .key_identifier()
+                    .map_or(true, |anchor_key_id| anchor_key_id == key_id.as_bytes())
+            });
+            candidates.sort_by_key(|anchor| anchor.key_identifier().is_none());
+        }
+
+        candidates
+    }
+
+    fn resolve<'a>(
+        &'a self,
+        indices: Option<&'a Vec<usize>>,
+    ) -> impl Iterator<Item = &'a TrustAnchor> {
+        indices
+            .into_iter()
+            .flatten()
+            .map(move |&index| &self.anchors[index])
+    }
+}
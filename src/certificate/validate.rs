@@ -1,14 +1,309 @@
 use crate::Result;
 
-use super::{Certificate, CertificateChain, Error};
+use super::{
+    key_usage::{KeyPurpose, KeyUsagePolicy},
+    name_constraints::NameConstraintsState,
+    policy::PolicyState,
+    trust_anchor::TrustAnchor,
+    trust_store::TrustStore,
+    verify::SignatureVerifierRegistry,
+    Certificate, CertificateChain, Error,
+};
 
-use std::{iter, time::SystemTime};
+use std::{
+    collections::BTreeSet,
+    fmt,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
+use const_oid::{db::rfc5280::ANY_EXTENDED_KEY_USAGE, ObjectIdentifier};
+use pkcs8::spki::SubjectPublicKeyInfoOwned;
 use tracing::{debug, debug_span};
-use x509_cert::{ext::pkix::KeyUsages, time::Validity};
+use x509_cert::{ext::pkix::KeyUsages, name::Name, serial_number::SerialNumber, time::Validity};
+
+/// Source of the time at which certificates are validated
+pub trait TimeSource: Send + Sync {
+    fn now(&self) -> SystemTime;
+}
+
+/// Current system time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl TimeSource for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// Fixed point in time, e.g. the time of a signed timestamp
+impl TimeSource for SystemTime {
+    fn now(&self) -> SystemTime {
+        *self
+    }
+}
+
+/// Options for the validation of a certificate path
+#[derive(Clone)]
+pub struct ValidationOptions {
+    signature_verifiers: SignatureVerifierRegistry,
+    time_source: Arc<dyn TimeSource>,
+    not_before_skew: Duration,
+    not_after_grace: Duration,
+    initial_policy_set: Option<BTreeSet<ObjectIdentifier>>,
+    explicit_policy: bool,
+    inhibit_policy_mapping: bool,
+    inhibit_any_policy: bool,
+    required_extended_key_usages: BTreeSet<ObjectIdentifier>,
+    extended_key_usage_chaining: bool,
+    key_purposes: Vec<KeyPurpose>,
+    key_usage_policy: KeyUsagePolicy,
+    handled_extensions: BTreeSet<ObjectIdentifier>,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        Self {
+            signature_verifiers: SignatureVerifierRegistry::default(),
+            time_source: Arc::new(SystemClock),
+            not_before_skew: Duration::ZERO,
+            not_after_grace: Duration::ZERO,
+            initial_policy_set: None,
+            explicit_policy: false,
+            inhibit_policy_mapping: false,
+            inhibit_any_policy: false,
+            required_extended_key_usages: BTreeSet::new(),
+            extended_key_usage_chaining: false,
+            key_purposes: Vec::new(),
+            key_usage_policy: KeyUsagePolicy::default(),
+            handled_extensions: BTreeSet::new(),
+        }
+    }
+}
+
+impl fmt::Debug for ValidationOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValidationOptions")
+            .field("signature_verifiers", &self.signature_verifiers)
+            .field("not_before_skew", &self.not_before_skew)
+            .field("not_after_grace", &self.not_after_grace)
+            .field("initial_policy_set", &self.initial_policy_set)
+            .field("explicit_policy", &self.explicit_policy)
+            .field("inhibit_policy_mapping", &self.inhibit_policy_mapping)
+            .field("inhibit_any_policy", &self.inhibit_any_policy)
+            .field(
+                "required_extended_key_usages",
+                &self.required_extended_key_usages,
+            )
+            .field(
+                "extended_key_usage_chaining",
+                &self.extended_key_usage_chaining,
+            )
+            .field("key_purposes", &self.key_purposes)
+            .field("key_usage_policy", &self.key_usage_policy)
+            .field("handled_extensions", &self.handled_extensions)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ValidationOptions {
+    /// Set the source of the time at which the certificates are validated
+    pub fn set_time_source(mut self, time_source: impl TimeSource + 'static) -> Self {
+        self.time_source = Arc::new(time_source);
+        self
+    }
+
+    pub fn time_source(&self) -> &dyn TimeSource {
+        self.time_source.as_ref()
+    }
+
+    /// Set the tolerance for a validation time before the notBefore time,
+    /// e.g. to accept clocks running behind
+    pub fn set_not_before_skew(mut self, skew: Duration) -> Self {
+        self.not_before_skew = skew;
+        self
+    }
+
+    pub fn not_before_skew(&self) -> Duration {
+        self.not_before_skew
+    }
+
+    /// Set the grace period for a validation time after the notAfter time
+    pub fn set_not_after_grace(mut self, grace: Duration) -> Self {
+        self.not_after_grace = grace;
+        self
+    }
+
+    pub fn not_after_grace(&self) -> Duration {
+        self.not_after_grace
+    }
+
+    /// Set the registry used to verify the certificate signatures
+    pub fn set_signature_verifiers(mut self, registry: SignatureVerifierRegistry) -> Self {
+        self.signature_verifiers = registry;
+        self
+    }
+
+    pub fn signature_verifiers(&self) -> &SignatureVerifierRegistry {
+        &self.signature_verifiers
+    }
+
+    /// Set the certificate policies acceptable to the user (user-initial-policy-set),
+    /// by default any policy is acceptable
+    pub fn set_initial_policy_set(
+        mut self,
+        policies: impl IntoIterator<Item = ObjectIdentifier>,
+    ) -> Self {
+        self.initial_policy_set = Some(policies.into_iter().collect());
+        self
+    }
+
+    pub fn initial_policy_set(&self) -> Option<&BTreeSet<ObjectIdentifier>> {
+        self.initial_policy_set.as_ref()
+    }
+
+    /// Require the path to be valid for at least one acceptable policy (initial-explicit-policy)
+    pub fn set_explicit_policy(mut self, explicit_policy: bool) -> Self {
+        self.explicit_policy = explicit_policy;
+        self
+    }
+
+    pub fn explicit_policy(&self) -> bool {
+        self.explicit_policy
+    }
+
+    /// Inhibit the policy mappings of the certificates (initial-policy-mapping-inhibit)
+    pub fn set_inhibit_policy_mapping(mut self, inhibit_policy_mapping: bool) -> Self {
+        self.inhibit_policy_mapping = inhibit_policy_mapping;
+        self
+    }
+
+    pub fn inhibit_policy_mapping(&self) -> bool {
+        self.inhibit_policy_mapping
+    }
+
+    /// Ignore anyPolicy in the certificate policies (initial-any-policy-inhibit)
+    pub fn set_inhibit_any_policy(mut self, inhibit_any_policy: bool) -> Self {
+        self.inhibit_any_policy = inhibit_any_policy;
+        self
+    }
+
+    pub fn inhibit_any_policy(&self) -> bool {
+        self.inhibit_any_policy
+    }
+
+    /// Set the extended key usages the leaf has to be valid for,
+    /// e.g. `ID_KP_SERVER_AUTH` for a TLS server certificate
+    ///
+    /// A certificate without the extended key usage extension is valid for any usage.
+    pub fn set_required_extended_key_usages(
+        mut self,
+        usages: impl IntoIterator<Item = ObjectIdentifier>,
+    ) -> Self {
+        self.required_extended_key_usages = usages.into_iter().collect();
+        self
+    }
+
+    pub fn required_extended_key_usages(&self) -> &BTreeSet<ObjectIdentifier> {
+        &self.required_extended_key_usages
+    }
+
+    /// Require the intermediates to be valid for the required extended key usages as well,
+    /// as browsers do
+    pub fn set_extended_key_usage_chaining(mut self, chaining: bool) -> Self {
+        self.extended_key_usage_chaining = chaining;
+        self
+    }
+
+    pub fn extended_key_usage_chaining(&self) -> bool {
+        self.extended_key_usage_chaining
+    }
+
+    /// Set the intended purposes of the leaf key, whose key usages are required by the policy
+    pub fn set_key_purposes(mut self, purposes: impl IntoIterator<Item = KeyPurpose>) -> Self {
+        self.key_purposes = purposes.into_iter().collect();
+        self
+    }
+
+    pub fn key_purposes(&self) -> &[KeyPurpose] {
+        &self.key_purposes
+    }
+
+    /// Set the key usages required for each purpose
+    pub fn set_key_usage_policy(mut self, policy: KeyUsagePolicy) -> Self {
+        self.key_usage_policy = policy;
+        self
+    }
+
+    pub fn key_usage_policy(&self) -> &KeyUsagePolicy {
+        &self.key_usage_policy
+    }
+
+    /// Set the OIDs of the critical extensions which are processed by the caller,
+    /// other unsupported critical extensions fail the validation
+    pub fn set_handled_extensions(
+        mut self,
+        oids: impl IntoIterator<Item = ObjectIdentifier>,
+    ) -> Self {
+        self.handled_extensions = oids.into_iter().collect();
+        self
+    }
+
+    pub fn handled_extensions(&self) -> &BTreeSet<ObjectIdentifier> {
+        &self.handled_extensions
+    }
+}
+
+/// Result of a successful path validation
+#[derive(Debug, Clone)]
+pub struct ValidatedPath<'a> {
+    trust_anchor: &'a TrustAnchor,
+    authorities_constrained_policies: BTreeSet<ObjectIdentifier>,
+    user_constrained_policies: BTreeSet<ObjectIdentifier>,
+}
+
+impl<'a> ValidatedPath<'a> {
+    /// Trust anchor the path was validated with
+    pub fn trust_anchor(&self) -> &'a TrustAnchor {
+        self.trust_anchor
+    }
+
+    /// Policies the path is valid for (authorities-constrained-policy-set)
+    ///
+    /// The policies are in the domain of the trust anchor, anyPolicy means the path is valid
+    /// for any policy and an empty set means it is not valid for any policy.
+    pub fn authorities_constrained_policies(&self) -> &BTreeSet<ObjectIdentifier> {
+        &self.authorities_constrained_policies
+    }
+
+    /// Policies the path is valid for which are acceptable to the user
+    /// (user-constrained-policy-set)
+    pub fn user_constrained_policies(&self) -> &BTreeSet<ObjectIdentifier> {
+        &self.user_constrained_policies
+    }
+}
 
 impl Certificate {
     pub fn validate_period(&self) -> Result<()> {
+        self.validate_period_at(SystemTime::now())
+    }
+
+    /// Validate the period of the certificate at the given time
+    pub fn validate_period_at(&self, time: SystemTime) -> Result<()> {
+        self.check_period(time, Duration::ZERO, Duration::ZERO)
+    }
+
+    /// Validate the period of the certificate at the time and with the tolerances of the options
+    pub fn validate_period_with(&self, options: &ValidationOptions) -> Result<()> {
+        self.check_period(
+            options.time_source().now(),
+            options.not_before_skew,
+            options.not_after_grace,
+        )
+    }
+
+    fn check_period(&self, time: SystemTime, skew: Duration, grace: Duration) -> Result<()> {
         let _span = debug_span!("validate_cert_period").entered();
 
         let Validity {
@@ -19,17 +314,23 @@ impl Certificate {
         debug!(
             not_before = %not_before,
             not_after = %not_after,
+            skew = ?skew,
+            grace = ?grace,
             "validating certificate period"
         );
 
-        let now = SystemTime::now();
-
-        if not_before.to_system_time() > now {
-            return Err(Error::CertificateImmature)?;
+        // Time left until the certificate becomes valid
+        if let Ok(remaining) = not_before.to_system_time().duration_since(time) {
+            if remaining > skew {
+                return Err(Error::CertificateImmature(remaining))?;
+            }
         }
 
-        if not_after.to_system_time() < now {
-            return Err(Error::CertificateExpired)?;
+        // Time since the certificate expired
+        if let Ok(elapsed) = time.duration_since(not_after.to_system_time()) {
+            if elapsed > grace {
+                return Err(Error::CertificateExpired(elapsed))?;
+            }
         }
 
         Ok(())
@@ -38,27 +339,122 @@ impl Certificate {
 
 impl CertificateChain {
     pub fn validate_period(&self) -> Result<()> {
+        self.validate_period_at(SystemTime::now())
+    }
+
+    /// Validate the period of all certificates in the chain at the given time
+    pub fn validate_period_at(&self, time: SystemTime) -> Result<()> {
+        self.check_period(time, Duration::ZERO, Duration::ZERO)
+    }
+
+    /// Validate the period of all certificates in the chain
+    /// at the time and with the tolerances of the options
+    pub fn validate_period_with(&self, options: &ValidationOptions) -> Result<()> {
+        self.check_period(
+            options.time_source().now(),
+            options.not_before_skew,
+            options.not_after_grace,
+        )
+    }
+
+    /// Check the periods in the order of the path validation, from the top to the leaf
+    fn check_period(&self, time: SystemTime, skew: Duration, grace: Duration) -> Result<()> {
         let _span = debug_span!("validate_chain_period").entered();
 
-        self.leaf.validate_period()?;
+        self.iter()
+            .try_for_each(|cert| cert.check_period(time, skew, grace))
+    }
 
-        self.intermediates
-            .iter()
-            .try_for_each(|cert| cert.validate_period())?;
+    pub fn validate_path(&self, trust_anchor: &Certificate) -> Result<()> {
+        self.validate_path_with(&trust_anchor.into(), &ValidationOptions::default())?;
+        Ok(())
+    }
 
+    /// Validate the path from the trust anchor to the leaf at the given time
+    pub fn validate_path_at(&self, trust_anchor: &Certificate, time: SystemTime) -> Result<()> {
+        self.validate_path_with(
+            &trust_anchor.into(),
+            &ValidationOptions::default().set_time_source(time),
+        )?;
         Ok(())
     }
 
-    pub fn validate_path(&self, trust_anchor: &Certificate) -> Result<()> {
+    /// Validate the path to the leaf from a trust anchor of the trust store
+    ///
+    /// The trust anchors are selected by the issuer name and authority key identifier
+    /// of the topmost certificate and tried in turn until the path validates.
+    pub fn validate_path_in<'a>(
+        &self,
+        trust_store: &'a TrustStore,
+        options: &ValidationOptions,
+    ) -> Result<ValidatedPath<'a>> {
+        let _span = debug_span!("validate_path_in", trust_anchors = trust_store.len()).entered();
+
+        let top = self.intermediates.first().unwrap_or(&self.leaf);
+
+        let mut result = Err(Error::TrustAnchorNotFound.into());
+        for trust_anchor in trust_store.find_issuers(top) {
+            debug!(
+                name = %trust_anchor.name(),
+                "trying trust anchor"
+            );
+            match self.validate_path_with(trust_anchor, options) {
+                Ok(validated) => return Ok(validated),
+                Err(err) => {
+                    debug!(error = %err, "trust anchor rejected");
+                    result = Err(err);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Validate the path from the trust anchor to the leaf with the given options
+    pub fn validate_path_with<'a>(
+        &self,
+        trust_anchor: &'a TrustAnchor,
+        options: &ValidationOptions,
+    ) -> Result<ValidatedPath<'a>> {
         let _span =
             debug_span!("validate_path", path_length = self.intermediates.len() + 1).entered();
 
-        let mut chain = iter::once(trust_anchor).chain(self.iter()).peekable();
+        // All certificates are validated at the same point in time
+        let time = options.time_source().now();
 
         let mut path_len_constraints: Vec<Option<u8>> =
             Vec::with_capacity(self.intermediates.len() + 1);
 
-        // 1. For each certificate in the chain
+        let mut name_constraints = NameConstraintsState::default();
+
+        let mut policies = PolicyState::new(self.intermediates.len() + 1, options, trust_anchor);
+
+        // 1. Check the first certificate against the trust anchor.
+        //    Only the constraints carried by the trust anchor are applied,
+        //    its validity period and key usage are not checked.
+        {
+            let first = self.intermediates.first().unwrap_or(&self.leaf);
+
+            let _span2 = debug_span!(
+                parent: &_span,
+                "trust_anchor",
+                name = %trust_anchor.name(),
+                path_len_constraint = trust_anchor.path_len_constraint(),
+            )
+            .entered();
+
+            path_len_constraints.push(trust_anchor.path_len_constraint());
+
+            if let Some(nc) = trust_anchor.name_constraints() {
+                name_constraints.add(nc);
+            }
+
+            check_issued_by(trust_anchor.into(), first, options)?;
+        }
+
+        let mut chain = self.iter().peekable();
+
+        // 2. For each certificate in the chain
         while let Some(current) = chain.next() {
             let _span2 = debug_span!(
                 parent: &_span,
@@ -68,10 +464,22 @@ impl CertificateChain {
             )
             .entered();
 
-            // 1.1. Check the current certificate validity period.
-            current.validate_period()?;
+            // 2.1. Check the current certificate validity period.
+            current.check_period(time, options.not_before_skew, options.not_after_grace)?;
+
+            // 2.2. Check the names of the current certificate against the name constraints
+            //      of the previous certificates, unless it is a self-issued intermediate.
+            let self_issued =
+                current.inner.tbs_certificate.subject == current.inner.tbs_certificate.issuer;
+            if !self_issued || chain.peek().is_none() {
+                debug!("checking name constraints for current certificate");
+                name_constraints.check(current, chain.peek().is_none())?;
+            }
+
+            // 2.3. Process the certificate policies of the current certificate.
+            policies.process(current, chain.peek().is_none())?;
 
-            // 1.2. Doing checks against the next certificate in the chain.
+            // 2.4. Doing checks against the next certificate in the chain.
             if let Some(next) = chain.peek() {
                 let _span3 = debug_span!(
                     parent: &_span2,
@@ -81,7 +489,7 @@ impl CertificateChain {
                 )
                 .entered();
 
-                // 1.2.1. Check the basic constraints of the current certificate.
+                // 2.4.1. Check the basic constraints of the current certificate.
                 //        If the certificate is a CA certificate, store the path length constraint.
                 if let Some(bc) = current.get_basic_constraints() {
                     debug!(
@@ -96,7 +504,7 @@ impl CertificateChain {
                     path_len_constraints.push(bc.path_len_constraint);
                 }
 
-                // 1.2.3. Check the key usage of the current certificate.
+                // 2.4.2. Check the key usage of the current certificate.
                 //        If the key usage extension and the keyCertSign bit is present.
                 if let Some(ku) = current.get_key_usage() {
                     debug!(
@@ -108,42 +516,47 @@ impl CertificateChain {
                     }
                 }
 
-                // 1.2.4. Check the issuer of the current certificate
-                //        against the subject of the next certificate.
-                debug!("comparing issuer and subject");
-                if current.inner.tbs_certificate.subject != next.inner.tbs_certificate.issuer {
-                    return Err(Error::IssuerSubjectMismatch)?;
+                // 2.4.3. Check that the next certificate is issued by the current certificate.
+                check_issued_by(current.into(), next, options)?;
+
+                // 2.4.4. Store the name constraints of the current certificate.
+                if let Some(nc) = current.get_name_constraints() {
+                    name_constraints.add(nc);
                 }
 
-                // 1.2.5. Check the subject key identifier of the current certificate
-                //        against the authority key identifier of the next certificate.
-                if let Some(aki) = next.get_authority_key_identifier() {
-                    if let Some(ki) = &aki.key_identifier {
-                        if let Some(ski) = current.get_subject_key_identifier() {
-                            debug!(
-                                "comparing subject key identifier with authority key identifier"
-                            );
-                            if ki != &ski.0 {
-                                return Err(Error::AuthorityKeyIdentifierMismatch)?;
-                            }
-                        }
-                    }
+                // 2.4.5. Process the policy mappings and constraints of the current certificate.
+                policies.prepare_next(current)?;
 
-                    if let Some(acsn) = &aki.authority_cert_serial_number {
-                        debug!("comparing serial number with authority cert serial number");
-                        if acsn != &current.inner.tbs_certificate.serial_number {
-                            return Err(Error::AuthorityKeyIdentifierMismatch)?;
-                        }
-                    }
+                // 2.4.6. Check the extended key usage of the current certificate,
+                //        if the extended key usages are chained.
+                if options.extended_key_usage_chaining {
+                    check_extended_key_usage(current, &options.required_extended_key_usages)?;
                 }
+            } else {
+                // 2.5. Check the extended key usage of the leaf
+                //      and its key usage for the intended purposes.
+                check_extended_key_usage(current, &options.required_extended_key_usages)?;
+
+                debug!("checking key usage for the intended purposes");
+                options
+                    .key_usage_policy
+                    .check(current, &options.key_purposes)?;
+            }
 
-                // 1.2.6. Check the signature of the next certificate
-                //        with the public key of the current certificate.
-                current.verify_signature(next)?;
+            // 2.6. Check that all critical extensions of the current certificate are processed.
+            let unprocessed: Vec<_> = current
+                .unsupported_critical_extensions
+                .iter()
+                .filter(|oid| !options.handled_extensions.contains(oid))
+                .copied()
+                .collect();
+            if !unprocessed.is_empty() {
+                debug!(extensions = ?unprocessed, "unprocessed critical extensions");
+                return Err(Error::UnprocessedCriticalExtensions(unprocessed).into());
             }
         }
 
-        // 2. Check the path length constraint for the entire chain.
+        // 3. Check the path length constraint for the entire chain.
         //    For each CA certificate, ensure that the remaining certificates
         //    in the chain do not exceed its path length constraint.
         debug!("checking path length constraint for the entire chain");
@@ -156,6 +569,108 @@ impl CertificateChain {
             }
         }
 
-        Ok(())
+        // 4. Finish the policy processing with the leaf.
+        let (authorities_constrained_policies, user_constrained_policies) =
+            policies.finish(&self.leaf)?;
+
+        Ok(ValidatedPath {
+            trust_anchor,
+            authorities_constrained_policies,
+            user_constrained_policies,
+        })
+    }
+}
+
+/// Issuer of a certificate in the path, either a trust anchor or a CA certificate
+struct Issuer<'a> {
+    name: &'a Name,
+    key_identifier: Option<&'a [u8]>,
+    serial_number: Option<&'a SerialNumber>,
+    public_key: &'a SubjectPublicKeyInfoOwned,
+}
+
+impl<'a> From<&'a TrustAnchor> for Issuer<'a> {
+    fn from(trust_anchor: &'a TrustAnchor) -> Self {
+        Self {
+            name: trust_anchor.name(),
+            key_identifier: trust_anchor.key_identifier(),
+            serial_number: trust_anchor.serial_number(),
+            public_key: trust_anchor.public_key(),
+        }
+    }
+}
+
+impl<'a> From<&'a Certificate> for Issuer<'a> {
+    fn from(cert: &'a Certificate) -> Self {
+        Self {
+            name: &cert.inner.tbs_certificate.subject,
+            key_identifier: cert
+                .get_subject_key_identifier()
+                .map(|ski| ski.0.as_bytes()),
+            serial_number: Some(&cert.inner.tbs_certificate.serial_number),
+            public_key: &cert.inner.tbs_certificate.subject_public_key_info,
+        }
+    }
+}
+
+/// Check that the certificate is issued by the issuer
+fn check_issued_by(
+    issuer: Issuer<'_>,
+    cert: &Certificate,
+    options: &ValidationOptions,
+) -> Result<()> {
+    // 1. Check the subject of the issuer against the issuer of the certificate.
+    debug!("comparing issuer and subject");
+    if issuer.name != &cert.inner.tbs_certificate.issuer {
+        return Err(Error::IssuerSubjectMismatch.into());
+    }
+
+    // 2. Check the key identifier of the issuer
+    //    against the authority key identifier of the certificate.
+    if let Some(aki) = cert.get_authority_key_identifier() {
+        if let (Some(ki), Some(ski)) = (&aki.key_identifier, issuer.key_identifier) {
+            debug!("comparing subject key identifier with authority key identifier");
+            if ki.as_bytes() != ski {
+                return Err(Error::AuthorityKeyIdentifierMismatch.into());
+            }
+        }
+
+        if let (Some(acsn), Some(serial_number)) =
+            (&aki.authority_cert_serial_number, issuer.serial_number)
+        {
+            debug!("comparing serial number with authority cert serial number");
+            if acsn != serial_number {
+                return Err(Error::AuthorityKeyIdentifierMismatch.into());
+            }
+        }
+    }
+
+    // 3. Check the signature of the certificate with the public key of the issuer.
+    options
+        .signature_verifiers()
+        .verify(issuer.public_key, cert)
+}
+
+/// Check that the certificate is valid for the required extended key usages
+fn check_extended_key_usage(
+    cert: &Certificate,
+    required: &BTreeSet<ObjectIdentifier>,
+) -> Result<()> {
+    // Without the extension or with anyExtendedKeyUsage, the certificate is valid for any usage
+    let Some(eku) = cert.get_extended_key_usage() else {
+        return Ok(());
+    };
+    if eku.0.contains(&ANY_EXTENDED_KEY_USAGE) {
+        return Ok(());
     }
+
+    debug!(
+        extended_key_usage = ?eku.0,
+        "checking extended key usage for current certificate"
+    );
+    if let Some(&usage) = required.iter().find(|usage| !eku.0.contains(usage)) {
+        return Err(Error::ExtendedKeyUsageViolation(usage).into());
+    }
+
+    Ok(())
 }
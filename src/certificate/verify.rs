@@ -1,15 +1,38 @@
 use crate::Result;
 
-#[cfg(feature = "signature")]
-use crate::signature::SignatureVerifier;
+#[cfg(any(feature = "ed25519", feature = "ecdsa", feature = "rsa"))]
+use crate::signature::{FromPublicKeyInfo, SignatureVerifier};
 
 #[cfg(feature = "ed25519")]
 use crate::signature::ed25519::{self, ED_25519_OID};
 
+#[cfg(feature = "rsa")]
+use crate::signature::rsa;
+
+#[cfg(feature = "ecdsa")]
+use crate::signature::ecdsa::{self, ECDSA_SHA256_OID, ECDSA_SHA384_OID, ECDSA_SHA512_OID};
+
 use super::{Certificate, Error};
 
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, OnceLock},
+};
+
 #[cfg(feature = "ecdsa")]
-use ecdsa::{ECDSA_SHA256_OID, ECDSA_SHA384_OID};
+use ::ecdsa::{
+    elliptic_curve::{
+        sec1::{FromEncodedPoint, ModulusSize, ToEncodedPoint},
+        AffinePoint, CurveArithmetic, FieldBytesSize,
+    },
+    PrimeCurve,
+};
+
+use const_oid::{
+    db::{rfc5912, rfc8410},
+    ObjectIdentifier,
+};
 
 #[cfg(feature = "ecdsa")]
 use p256::NistP256;
@@ -17,12 +40,194 @@ use p256::NistP256;
 #[cfg(feature = "ecdsa")]
 use p384::NistP384;
 
-use pkcs8::der::referenced::OwnedToRef;
+#[cfg(feature = "ecdsa")]
+use p521::NistP521;
+
+#[cfg(feature = "secp256k1")]
+use k256::Secp256k1;
+
+#[cfg(any(feature = "rsa", feature = "ecdsa"))]
+use const_oid::AssociatedOid;
+
+#[cfg(any(feature = "rsa", feature = "ecdsa"))]
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+#[cfg(feature = "rsa")]
+use sha2::digest::FixedOutputReset;
+
+use pkcs8::{
+    der::referenced::OwnedToRef,
+    spki::{AlgorithmIdentifierRef, SubjectPublicKeyInfoOwned},
+};
+#[cfg(any(feature = "ed25519", feature = "ecdsa", feature = "rsa"))]
 use tracing::debug;
 
+/// Signature algorithms which may legitimately be produced by a public key algorithm
+const COMPATIBLE_ALGORITHMS: &[(ObjectIdentifier, &[ObjectIdentifier])] = &[
+    // Ed25519 (RFC 8410)
+    (rfc8410::ID_ED_25519, &[rfc8410::ID_ED_25519]),
+    // Elliptic curve keys (RFC 5480)
+    (
+        rfc5912::ID_EC_PUBLIC_KEY,
+        &[
+            rfc5912::ECDSA_WITH_SHA_256,
+            rfc5912::ECDSA_WITH_SHA_384,
+            rfc5912::ECDSA_WITH_SHA_512,
+        ],
+    ),
+    // RSA keys without restrictions (RFC 4055)
+    (
+        rfc5912::RSA_ENCRYPTION,
+        &[
+            rfc5912::SHA_256_WITH_RSA_ENCRYPTION,
+            rfc5912::SHA_384_WITH_RSA_ENCRYPTION,
+            rfc5912::SHA_512_WITH_RSA_ENCRYPTION,
+            rfc5912::ID_RSASSA_PSS,
+        ],
+    ),
+    // RSA keys restricted to RSASSA-PSS (RFC 4055)
+    (rfc5912::ID_RSASSA_PSS, &[rfc5912::ID_RSASSA_PSS]),
+];
+
+/// Get the signature algorithms which may be produced by a key of the given public key algorithm
+fn compatible_algorithms(key_algo: ObjectIdentifier) -> Option<&'static [ObjectIdentifier]> {
+    COMPATIBLE_ALGORITHMS
+        .iter()
+        .find_map(|(key, algos)| (*key == key_algo).then_some(*algos))
+}
+
+/// Verifier for the signatures of certificates
+///
+/// Verifiers are registered in a [`SignatureVerifierRegistry`] for the
+/// signature algorithms they are able to verify.
+pub trait CertificateSignatureVerifier: Send + Sync {
+    /// Verify the signature of the given certificate with the public key of its issuer
+    fn verify(&self, issuer_key: &SubjectPublicKeyInfoOwned, cert: &Certificate) -> Result<()>;
+
+    /// Check if a key of the given public key algorithm may produce the given signature algorithm
+    ///
+    /// The default implementation only knows the algorithms of RFC 5912 and RFC 8410,
+    /// so verifiers for other key types have to override it.
+    fn is_compatible(
+        &self,
+        key_algo: AlgorithmIdentifierRef<'_>,
+        signature_algo: ObjectIdentifier,
+    ) -> bool {
+        compatible_algorithms(key_algo.oid).is_some_and(|algos| algos.contains(&signature_algo))
+    }
+}
+
+/// Registry of certificate signature verifiers keyed by signature algorithm
+///
+/// The default registry contains all algorithms enabled by crate features.
+#[derive(Clone)]
+pub struct SignatureVerifierRegistry {
+    verifiers: HashMap<ObjectIdentifier, Arc<dyn CertificateSignatureVerifier>>,
+}
+
+impl SignatureVerifierRegistry {
+    /// Create a registry without any algorithms
+    pub fn empty() -> Self {
+        Self {
+            verifiers: HashMap::new(),
+        }
+    }
+
+    /// Register a verifier for the given signature algorithm.
+    /// A previously registered verifier for the algorithm is replaced.
+    pub fn register(
+        &mut self,
+        algorithm: ObjectIdentifier,
+        verifier: impl CertificateSignatureVerifier + 'static,
+    ) -> &mut Self {
+        self.verifiers.insert(algorithm, Arc::new(verifier));
+        self
+    }
+
+    /// Remove the verifier of the given signature algorithm
+    pub fn unregister(&mut self, algorithm: &ObjectIdentifier) -> &mut Self {
+        self.verifiers.remove(algorithm);
+        self
+    }
+
+    pub fn get(&self, algorithm: &ObjectIdentifier) -> Option<&dyn CertificateSignatureVerifier> {
+        self.verifiers.get(algorithm).map(AsRef::as_ref)
+    }
+
+    pub fn algorithms(&self) -> impl Iterator<Item = &ObjectIdentifier> {
+        self.verifiers.keys()
+    }
+
+    /// Verify the signature of the given certificate with the public key of its issuer
+    pub fn verify(&self, issuer_key: &SubjectPublicKeyInfoOwned, cert: &Certificate) -> Result<()> {
+        let algo = cert.inner.signature_algorithm.oid;
+
+        let verifier = self.get(&algo).ok_or(Error::AlgorithmUnsupported)?;
+
+        // Check if the issuer key may produce the signature algorithm
+        if !verifier.is_compatible(issuer_key.algorithm.owned_to_ref(), algo) {
+            return Err(Error::AlgorithmMismatch)?;
+        }
+
+        verifier.verify(issuer_key, cert)
+    }
+}
+
+impl Default for SignatureVerifierRegistry {
+    fn default() -> Self {
+        builtin_registry().clone()
+    }
+}
+
+impl fmt::Debug for SignatureVerifierRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignatureVerifierRegistry")
+            .field("algorithms", &self.verifiers.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Registry of the built-in algorithms enabled by crate features
+fn builtin_registry() -> &'static SignatureVerifierRegistry {
+    static REGISTRY: OnceLock<SignatureVerifierRegistry> = OnceLock::new();
+
+    REGISTRY.get_or_init(|| {
+        #[allow(unused_mut)]
+        let mut registry = SignatureVerifierRegistry::empty();
+
+        #[cfg(feature = "ed25519")]
+        registry.register(ED_25519_OID, Ed25519Verifier);
+
+        #[cfg(feature = "ecdsa")]
+        registry
+            .register(ECDSA_SHA256_OID, EcdsaVerifier)
+            .register(ECDSA_SHA384_OID, EcdsaVerifier)
+            .register(ECDSA_SHA512_OID, EcdsaVerifier);
+
+        #[cfg(feature = "rsa")]
+        registry
+            .register(rfc5912::SHA_256_WITH_RSA_ENCRYPTION, RsaPkcs1v15Verifier)
+            .register(rfc5912::SHA_384_WITH_RSA_ENCRYPTION, RsaPkcs1v15Verifier)
+            .register(rfc5912::SHA_512_WITH_RSA_ENCRYPTION, RsaPkcs1v15Verifier)
+            .register(rfc5912::ID_RSASSA_PSS, RsaPssVerifier);
+
+        registry
+    })
+}
+
 impl Certificate {
     /// Verify if given certificate is signed by current certificate
     pub fn verify_signature(&self, cert: &Certificate) -> Result<()> {
+        self.verify_signature_with(cert, builtin_registry())
+    }
+
+    /// Verify if given certificate is signed by current certificate
+    /// with the verifiers of the given registry
+    pub fn verify_signature_with(
+        &self,
+        cert: &Certificate,
+        registry: &SignatureVerifierRegistry,
+    ) -> Result<()> {
         let _span = tracing::trace_span!(
             "verify_signature",
             issuer = %self.inner.tbs_certificate.subject,
@@ -30,53 +235,164 @@ impl Certificate {
         )
         .entered();
 
-        let algo = self
-            .inner
-            .tbs_certificate
-            .subject_public_key_info
-            .algorithm
-            .owned_to_ref();
+        registry.verify(&self.inner.tbs_certificate.subject_public_key_info, cert)
+    }
+}
 
-        // Compare algorithm identifiers
-        if algo != cert.inner.signature_algorithm.owned_to_ref() {
-            return Err(Error::AlgorithmMismatch)?;
+/// Ed25519 (RFC 8410)
+#[cfg(feature = "ed25519")]
+struct Ed25519Verifier;
+
+#[cfg(feature = "ed25519")]
+impl CertificateSignatureVerifier for Ed25519Verifier {
+    fn verify(&self, issuer_key: &SubjectPublicKeyInfoOwned, cert: &Certificate) -> Result<()> {
+        debug!("verifying signature with Ed25519 algorithm");
+        let key = ed25519::VerifyingKey::from_public_key_info(issuer_key)?;
+        let verifier = SignatureVerifier::new(key);
+        verifier.verify_certificate(cert)
+    }
+}
+
+/// ECDSA (RFC 5758)
+#[cfg(feature = "ecdsa")]
+struct EcdsaVerifier;
+
+#[cfg(feature = "ecdsa")]
+impl CertificateSignatureVerifier for EcdsaVerifier {
+    fn verify(&self, issuer_key: &SubjectPublicKeyInfoOwned, cert: &Certificate) -> Result<()> {
+        let algo = cert.inner.signature_algorithm.oid;
+        let curve = issuer_key.algorithm.owned_to_ref().parameters_oid()?;
+
+        debug!(
+            curve = %curve,
+            hash = %algo,
+            "verifying signature with ECDSA algorithm"
+        );
+        match algo {
+            ECDSA_SHA256_OID => verify_ecdsa::<Sha256>(issuer_key, cert, curve),
+            ECDSA_SHA384_OID => verify_ecdsa::<Sha384>(issuer_key, cert, curve),
+            ECDSA_SHA512_OID => verify_ecdsa::<Sha512>(issuer_key, cert, curve),
+            _ => Err(Error::AlgorithmUnsupported)?,
         }
+    }
+}
 
-        // Verify signature
-        match algo.oid {
-            // Ed25519 (RFC 8410)
-            #[cfg(feature = "ed25519")]
-            ED_25519_OID => {
-                debug!("verifying signature with Ed25519 algorithm");
-                let key = ed25519::VerifyingKey::try_from(self)?;
-                let verifier = SignatureVerifier::new(key);
-                verifier.verify_certificate(cert)?;
-            }
+/// RSA PKCS#1 v1.5 (RFC 8017)
+#[cfg(feature = "rsa")]
+struct RsaPkcs1v15Verifier;
 
-            // ECDSA with SHA-256 (RFC 5912)
-            #[cfg(feature = "ecdsa")]
-            ECDSA_SHA256_OID => {
-                debug!("verifying signature with ECDSA with SHA-256 algorithm");
-                let key = ecdsa::VerifyingKey::<NistP256>::try_from(self)?;
-                let verifier = SignatureVerifier::<_, ecdsa::Signature<NistP256>>::new(key);
-                verifier.verify_certificate(cert)?;
-            }
+#[cfg(feature = "rsa")]
+impl CertificateSignatureVerifier for RsaPkcs1v15Verifier {
+    fn verify(&self, issuer_key: &SubjectPublicKeyInfoOwned, cert: &Certificate) -> Result<()> {
+        let algo = cert.inner.signature_algorithm.oid;
 
-            // ECDSA with SHA-384 (RFC 5912)
-            #[cfg(feature = "ecdsa")]
-            ECDSA_SHA384_OID => {
-                debug!("verifying signature with ECDSA with SHA-384 algorithm");
-                let key = ecdsa::VerifyingKey::<NistP384>::try_from(self)?;
-                let verifier = SignatureVerifier::<_, ecdsa::Signature<NistP384>>::new(key);
-                verifier.verify_certificate(cert)?;
-            }
+        debug!(hash = %algo, "verifying signature with RSA PKCS#1 v1.5 algorithm");
+        match algo {
+            rfc5912::SHA_256_WITH_RSA_ENCRYPTION => verify_rsa_pkcs1v15::<Sha256>(issuer_key, cert),
+            rfc5912::SHA_384_WITH_RSA_ENCRYPTION => verify_rsa_pkcs1v15::<Sha384>(issuer_key, cert),
+            rfc5912::SHA_512_WITH_RSA_ENCRYPTION => verify_rsa_pkcs1v15::<Sha512>(issuer_key, cert),
+            _ => Err(Error::AlgorithmUnsupported)?,
+        }
+    }
+}
+
+/// RSASSA-PSS (RFC 4055)
+#[cfg(feature = "rsa")]
+struct RsaPssVerifier;
 
-            // Unsupported
-            _ => {
-                return Err(Error::AlgorithmUnsupported)?;
+#[cfg(feature = "rsa")]
+impl CertificateSignatureVerifier for RsaPssVerifier {
+    fn verify(&self, issuer_key: &SubjectPublicKeyInfoOwned, cert: &Certificate) -> Result<()> {
+        let params = rsa::PssParams::try_from(cert)?;
+
+        // Check the parameters against the restrictions of the issuer key
+        let key_algo = issuer_key.algorithm.owned_to_ref();
+        if key_algo.oid == rfc5912::ID_RSASSA_PSS {
+            if let Some(restrictions) = rsa::PssParams::from_algorithm(key_algo)? {
+                if !params.complies_with(&restrictions) {
+                    return Err(Error::AlgorithmMismatch)?;
+                }
             }
         }
 
-        Ok(())
+        debug!(
+            hash = %params.hash,
+            salt_len = params.salt_len,
+            "verifying signature with RSASSA-PSS algorithm"
+        );
+        match params.hash {
+            Sha256::OID => verify_rsassa_pss::<Sha256>(issuer_key, cert, params.salt_len),
+            Sha384::OID => verify_rsassa_pss::<Sha384>(issuer_key, cert, params.salt_len),
+            Sha512::OID => verify_rsassa_pss::<Sha512>(issuer_key, cert, params.salt_len),
+            _ => Err(Error::AlgorithmUnsupported)?,
+        }
+    }
+}
+
+#[cfg(feature = "rsa")]
+fn verify_rsa_pkcs1v15<D>(issuer_key: &SubjectPublicKeyInfoOwned, cert: &Certificate) -> Result<()>
+where
+    D: Digest + AssociatedOid,
+{
+    let key = rsa::VerifyingKey::<D>::from_public_key_info(issuer_key)?;
+    let verifier = SignatureVerifier::<_, rsa::Signature>::new(key);
+    verifier.verify_certificate(cert)
+}
+
+#[cfg(feature = "rsa")]
+fn verify_rsassa_pss<D>(
+    issuer_key: &SubjectPublicKeyInfoOwned,
+    cert: &Certificate,
+    salt_len: u8,
+) -> Result<()>
+where
+    D: Digest + FixedOutputReset,
+{
+    let key = rsa::PssVerifyingKey::<D>::new_with_salt_len(
+        rsa::RsaPublicKey::from_public_key_info(issuer_key)?,
+        salt_len.into(),
+    );
+    let verifier = SignatureVerifier::<_, rsa::PssSignature>::new(key);
+    verifier.verify_certificate(cert)
+}
+
+/// Verify an ECDSA signature with the curve of the issuer key
+/// and the digest of the signature algorithm
+#[cfg(feature = "ecdsa")]
+fn verify_ecdsa<D>(
+    issuer_key: &SubjectPublicKeyInfoOwned,
+    cert: &Certificate,
+    curve: ObjectIdentifier,
+) -> Result<()>
+where
+    D: Digest,
+{
+    match curve {
+        NistP256::OID => verify_ecdsa_with_curve::<NistP256, D>(issuer_key, cert),
+        NistP384::OID => verify_ecdsa_with_curve::<NistP384, D>(issuer_key, cert),
+        NistP521::OID => verify_ecdsa_with_curve::<NistP521, D>(issuer_key, cert),
+        #[cfg(feature = "secp256k1")]
+        Secp256k1::OID => verify_ecdsa_with_curve::<Secp256k1, D>(issuer_key, cert),
+        _ => Err(Error::AlgorithmUnsupported)?,
     }
 }
+
+#[cfg(feature = "ecdsa")]
+fn verify_ecdsa_with_curve<C, D>(
+    issuer_key: &SubjectPublicKeyInfoOwned,
+    cert: &Certificate,
+) -> Result<()>
+where
+    C: PrimeCurve + CurveArithmetic + AssociatedOid,
+    FieldBytesSize<C>: ModulusSize,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    for<'a> ecdsa::Signature<C>: TryFrom<&'a Certificate, Error = crate::signature::Error>,
+    ecdsa::DigestVerifyingKey<C, D>: ::signature::Verifier<ecdsa::Signature<C>>,
+{
+    let key = ecdsa::VerifyingKey::<C>::from_public_key_info(issuer_key)?;
+    let verifier = SignatureVerifier::<_, ecdsa::Signature<C>>::new(ecdsa::DigestVerifyingKey::<
+        C,
+        D,
+    >::new(key));
+    verifier.verify_certificate(cert)
+}
@@ -1,23 +1,36 @@
 use crate::certificate::Certificate;
 
-use super::Error;
+use super::{Error, FromPublicKeyInfo};
 
-use std::{convert::TryFrom, ops::Add};
+use std::{convert::TryFrom, marker::PhantomData, ops::Add};
 
-use const_oid::AssociatedOid;
+use const_oid::{AssociatedOid, ObjectIdentifier};
+// generic-array 0.14.9 deprecates `ArrayLength`, which the bounds of ecdsa 0.16 still require
+#[allow(deprecated)]
 use ecdsa::{
     der,
     elliptic_curve::{
-        generic_array::ArrayLength,
+        generic_array::{typenum::Unsigned, ArrayLength},
         sec1::{FromEncodedPoint, ModulusSize, ToEncodedPoint},
-        AffinePoint, CurveArithmetic, FieldBytesSize, ALGORITHM_OID,
+        AffinePoint, CurveArithmetic, FieldBytesSize,
     },
-    PrimeCurve,
+    hazmat::VerifyPrimitive,
+    PrimeCurve, SignatureSize,
 };
-use pkcs8::der::referenced::OwnedToRef;
+use pkcs8::{der::referenced::OwnedToRef, spki::SubjectPublicKeyInfoOwned};
+use sha2::Digest;
+use signature::{hazmat::PrehashVerifier, Verifier};
 
-pub use ecdsa::{Signature, VerifyingKey};
+pub use ecdsa::{
+    elliptic_curve::ALGORITHM_OID as EC_PUBLIC_KEY_OID, Signature, VerifyingKey, ECDSA_SHA256_OID,
+    ECDSA_SHA384_OID, ECDSA_SHA512_OID,
+};
+
+/// Signature algorithms which can be produced by an elliptic curve key
+pub(crate) const ECDSA_OIDS: [ObjectIdentifier; 3] =
+    [ECDSA_SHA256_OID, ECDSA_SHA384_OID, ECDSA_SHA512_OID];
 
+#[allow(deprecated)]
 impl<C> TryFrom<&Certificate> for Signature<C>
 where
     C: PrimeCurve + AssociatedOid,
@@ -27,10 +40,10 @@ where
     type Error = Error;
 
     fn try_from(cert: &Certificate) -> Result<Self, Self::Error> {
-        cert.inner
-            .signature_algorithm
-            .owned_to_ref()
-            .assert_oids(ALGORITHM_OID, C::OID)?;
+        let oid = cert.inner.signature_algorithm.oid;
+        if !ECDSA_OIDS.contains(&oid) {
+            return Err(spki::Error::OidUnknown { oid })?;
+        }
 
         let bytes = cert
             .inner
@@ -51,15 +64,22 @@ where
     type Error = Error;
 
     fn try_from(cert: &Certificate) -> Result<Self, Self::Error> {
-        cert.inner
-            .signature_algorithm
+        Self::from_public_key_info(&cert.inner.tbs_certificate.subject_public_key_info)
+    }
+}
+
+impl<C> FromPublicKeyInfo for VerifyingKey<C>
+where
+    C: PrimeCurve + CurveArithmetic + AssociatedOid,
+    FieldBytesSize<C>: ModulusSize,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+{
+    fn from_public_key_info(info: &SubjectPublicKeyInfoOwned) -> Result<Self, Error> {
+        info.algorithm
             .owned_to_ref()
-            .assert_oids(ALGORITHM_OID, C::OID)?;
+            .assert_oids(EC_PUBLIC_KEY_OID, C::OID)?;
 
-        let bytes = cert
-            .inner
-            .tbs_certificate
-            .subject_public_key_info
+        let bytes = info
             .subject_public_key
             .as_bytes()
             .ok_or(spki::Error::KeyMalformed)?;
@@ -67,3 +87,53 @@ where
         Ok(Self::from_sec1_bytes(bytes)?)
     }
 }
+
+/// ECDSA verifying key which hashes messages with the digest `D`
+/// instead of the default digest of the curve `C`
+pub struct DigestVerifyingKey<C, D>
+where
+    C: PrimeCurve + CurveArithmetic,
+{
+    key: VerifyingKey<C>,
+    _marker: PhantomData<D>,
+}
+
+impl<C, D> DigestVerifyingKey<C, D>
+where
+    C: PrimeCurve + CurveArithmetic,
+{
+    pub fn new(key: VerifyingKey<C>) -> Self {
+        Self {
+            key,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[allow(deprecated)]
+impl<C, D> Verifier<Signature<C>> for DigestVerifyingKey<C, D>
+where
+    C: PrimeCurve + CurveArithmetic,
+    AffinePoint<C>: VerifyPrimitive<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+    D: Digest,
+{
+    fn verify(&self, msg: &[u8], signature: &Signature<C>) -> signature::Result<()> {
+        // Some curves (e.g. secp256k1) only accept signatures with a low S value,
+        // but X.509 signatures are valid with either S value.
+        let signature = &signature.normalize_s().unwrap_or_else(|| signature.clone());
+
+        let digest = D::digest(msg);
+
+        // Digests shorter than the field size are left-padded with zeros,
+        // which keeps their integer value as required by FIPS 186-5.
+        let field_len = FieldBytesSize::<C>::USIZE;
+        if digest.len() < field_len {
+            let mut prehash = vec![0; field_len];
+            prehash[field_len - digest.len()..].copy_from_slice(&digest);
+            return self.key.verify_prehash(&prehash, signature);
+        }
+
+        self.key.verify_prehash(&digest, signature)
+    }
+}
@@ -1,9 +1,9 @@
 use crate::certificate::Certificate;
 
-use super::Error;
+use super::{Error, FromPublicKeyInfo};
 
 use const_oid::ObjectIdentifier;
-use pkcs8::spki;
+use pkcs8::spki::{self, SubjectPublicKeyInfoOwned};
 
 pub use ed25519_dalek::{Signature, VerifyingKey};
 
@@ -34,16 +34,15 @@ impl TryFrom<&Certificate> for VerifyingKey {
     type Error = Error;
 
     fn try_from(cert: &Certificate) -> Result<Self, Self::Error> {
-        cert.inner
-            .tbs_certificate
-            .subject_public_key_info
-            .algorithm
-            .assert_algorithm_oid(ED_25519_OID)?;
+        Self::from_public_key_info(&cert.inner.tbs_certificate.subject_public_key_info)
+    }
+}
 
-        let bytes = cert
-            .inner
-            .tbs_certificate
-            .subject_public_key_info
+impl FromPublicKeyInfo for VerifyingKey {
+    fn from_public_key_info(info: &SubjectPublicKeyInfoOwned) -> Result<Self, Error> {
+        info.algorithm.assert_algorithm_oid(ED_25519_OID)?;
+
+        let bytes = info
             .subject_public_key
             .as_bytes()
             .ok_or(spki::Error::KeyMalformed)?
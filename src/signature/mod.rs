@@ -2,12 +2,17 @@
 pub mod ecdsa;
 #[cfg(feature = "ed25519")]
 pub mod ed25519;
+#[cfg(feature = "rsa")]
+pub mod rsa;
 
 use crate::{certificate::Certificate, Result};
 
 use std::marker::PhantomData;
 
-use pkcs8::der::{self, Encode};
+use pkcs8::{
+    der::{self, Encode},
+    spki::SubjectPublicKeyInfoOwned,
+};
 use signature::Verifier;
 
 // Error type for signature verification
@@ -16,6 +21,12 @@ pub enum Error {
     /// Malformed signature
     SignatureMalformed,
 
+    /// Unsupported RSASSA-PSS hash or mask generation function
+    PssParametersUnsupported,
+
+    /// RSA modulus smaller than the minimum size, with its size in bits
+    KeyTooSmall(usize),
+
     /// Signature error
     Signature(signature::Error),
 
@@ -30,6 +41,8 @@ impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::SignatureMalformed => f.write_str("signature malformed"),
+            Self::PssParametersUnsupported => f.write_str("RSASSA-PSS parameters unsupported"),
+            Self::KeyTooSmall(bits) => write!(f, "RSA key too small: {} bits", bits),
             Self::Signature(err) => write!(f, "signature error: {}", err),
             Self::Pkcs8(err) => write!(f, "PKCS#8 error: {}", err),
             Self::Ans1(err) => write!(f, "ASN.1 DER error: {}", err),
@@ -72,6 +85,11 @@ impl From<pkcs8::spki::Error> for Error {
     }
 }
 
+/// Decode a verifying key from a subject public key info
+pub trait FromPublicKeyInfo: Sized {
+    fn from_public_key_info(info: &SubjectPublicKeyInfoOwned) -> std::result::Result<Self, Error>;
+}
+
 pub struct SignatureVerifier<V, S>
 where
     V: Verifier<S>,
@@ -0,0 +1,187 @@
+use crate::certificate::Certificate;
+
+use super::{Error, FromPublicKeyInfo};
+
+use const_oid::{
+    db::rfc5912::{
+        ID_MGF_1, ID_RSASSA_PSS, RSA_ENCRYPTION, SHA_256_WITH_RSA_ENCRYPTION,
+        SHA_384_WITH_RSA_ENCRYPTION, SHA_512_WITH_RSA_ENCRYPTION,
+    },
+    AssociatedOid, ObjectIdentifier,
+};
+use pkcs8::{
+    der::referenced::OwnedToRef,
+    spki::{self, AlgorithmIdentifierRef, SubjectPublicKeyInfoOwned},
+};
+use rsa::{
+    pkcs1::{DecodeRsaPublicKey, RsaPssParams},
+    traits::PublicKeyParts,
+};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+pub use rsa::{
+    pkcs1v15::{Signature, VerifyingKey},
+    pss::{Signature as PssSignature, VerifyingKey as PssVerifyingKey},
+    RsaPublicKey,
+};
+
+/// Minimum size of the modulus of an RSA key in bits, as required by
+/// [NIST SP 800-131A](https://csrc.nist.gov/pubs/sp/800/131/a/r2/final)
+pub const MIN_MODULUS_BITS: usize = 2048;
+
+/// Signature algorithms which can be produced by an RSA key
+pub(crate) const PKCS1V15_OIDS: [ObjectIdentifier; 3] = [
+    SHA_256_WITH_RSA_ENCRYPTION,
+    SHA_384_WITH_RSA_ENCRYPTION,
+    SHA_512_WITH_RSA_ENCRYPTION,
+];
+
+/// Validated RSASSA-PSS parameters defined in [RFC 4055](https://www.rfc-editor.org/rfc/rfc4055)
+///
+/// Only MGF1 with the same hash algorithm as the message digest
+/// and the trailer field `0xbc` are supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PssParams {
+    /// Hash algorithm for the message digest and MGF1
+    pub hash: ObjectIdentifier,
+
+    /// Salt length in bytes
+    pub salt_len: u8,
+}
+
+impl PssParams {
+    /// Decode the RSASSA-PSS parameters of the given algorithm identifier
+    ///
+    /// Returns `None` if the algorithm identifier has no parameters.
+    pub fn from_algorithm(algo: AlgorithmIdentifierRef<'_>) -> Result<Option<Self>, Error> {
+        algo.assert_algorithm_oid(ID_RSASSA_PSS)?;
+
+        let params = match algo.parameters {
+            Some(params) => params.decode_as::<RsaPssParams<'_>>()?,
+            None => return Ok(None),
+        };
+
+        let hash = params.hash.oid;
+        if ![Sha256::OID, Sha384::OID, Sha512::OID].contains(&hash) {
+            return Err(Error::PssParametersUnsupported);
+        }
+
+        if params.mask_gen.oid != ID_MGF_1
+            || params.mask_gen.parameters.map(|mgf_hash| mgf_hash.oid) != Some(hash)
+        {
+            return Err(Error::PssParametersUnsupported);
+        }
+
+        Ok(Some(Self {
+            hash,
+            salt_len: params.salt_len,
+        }))
+    }
+
+    /// Check if the parameters of a signature comply with the
+    /// restrictions of a key as defined in [RFC 4055 Section 3.3](https://www.rfc-editor.org/rfc/rfc4055#section-3.3)
+    pub fn complies_with(&self, restrictions: &Self) -> bool {
+        self.hash == restrictions.hash && self.salt_len >= restrictions.salt_len
+    }
+}
+
+impl TryFrom<&Certificate> for PssParams {
+    type Error = Error;
+
+    fn try_from(cert: &Certificate) -> Result<Self, Self::Error> {
+        Self::from_algorithm(cert.inner.signature_algorithm.owned_to_ref())?
+            .ok_or(spki::Error::AlgorithmParametersMissing)
+            .map_err(Into::into)
+    }
+}
+
+impl TryFrom<&Certificate> for Signature {
+    type Error = Error;
+
+    fn try_from(cert: &Certificate) -> Result<Self, Self::Error> {
+        let oid = cert.inner.signature_algorithm.oid;
+        if !PKCS1V15_OIDS.contains(&oid) {
+            return Err(spki::Error::OidUnknown { oid })?;
+        }
+
+        let bytes = cert
+            .inner
+            .signature
+            .as_bytes()
+            .ok_or(signature::Error::new())?;
+
+        Ok(Self::try_from(bytes)?)
+    }
+}
+
+impl<D> TryFrom<&Certificate> for VerifyingKey<D>
+where
+    D: Digest + AssociatedOid,
+{
+    type Error = Error;
+
+    fn try_from(cert: &Certificate) -> Result<Self, Self::Error> {
+        Self::from_public_key_info(&cert.inner.tbs_certificate.subject_public_key_info)
+    }
+}
+
+impl<D> FromPublicKeyInfo for VerifyingKey<D>
+where
+    D: Digest + AssociatedOid,
+{
+    fn from_public_key_info(info: &SubjectPublicKeyInfoOwned) -> Result<Self, Error> {
+        info.algorithm.assert_algorithm_oid(RSA_ENCRYPTION)?;
+
+        Ok(Self::new(RsaPublicKey::from_public_key_info(info)?))
+    }
+}
+
+impl TryFrom<&Certificate> for PssSignature {
+    type Error = Error;
+
+    fn try_from(cert: &Certificate) -> Result<Self, Self::Error> {
+        cert.inner
+            .signature_algorithm
+            .assert_algorithm_oid(ID_RSASSA_PSS)?;
+
+        let bytes = cert
+            .inner
+            .signature
+            .as_bytes()
+            .ok_or(signature::Error::new())?;
+
+        Ok(Self::try_from(bytes)?)
+    }
+}
+
+impl TryFrom<&Certificate> for RsaPublicKey {
+    type Error = Error;
+
+    fn try_from(cert: &Certificate) -> Result<Self, Self::Error> {
+        Self::from_public_key_info(&cert.inner.tbs_certificate.subject_public_key_info)
+    }
+}
+
+impl FromPublicKeyInfo for RsaPublicKey {
+    fn from_public_key_info(info: &SubjectPublicKeyInfoOwned) -> Result<Self, Error> {
+        if ![RSA_ENCRYPTION, ID_RSASSA_PSS].contains(&info.algorithm.oid) {
+            return Err(spki::Error::OidUnknown {
+                oid: info.algorithm.oid,
+            })?;
+        }
+
+        let bytes = info
+            .subject_public_key
+            .as_bytes()
+            .ok_or(spki::Error::KeyMalformed)?;
+
+        let key = Self::from_pkcs1_der(bytes).map_err(|_| spki::Error::KeyMalformed)?;
+
+        let bits = key.n().bits();
+        if bits < MIN_MODULUS_BITS {
+            return Err(Error::KeyTooSmall(bits));
+        }
+
+        Ok(key)
+    }
+}
@@ -1,11 +1,113 @@
-use std::io;
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime},
+};
 
-use pki_rs::certificate::{Certificate, CertificateChainBuilder};
+use pki_rs::{
+    certificate::{
+        key_usage::{KeyPurpose, KeyUsagePolicy},
+        path_builder::PathBuilder,
+        spiffe::{SpiffeId, TrustBundleSet},
+        trust_anchor::TrustAnchor,
+        trust_store::TrustStore,
+        validate::ValidationOptions,
+        verify::{CertificateSignatureVerifier, SignatureVerifierRegistry},
+        Certificate, CertificateChain, CertificateChainBuilder, Error as CertificateError,
+    },
+    error::Error,
+    signature,
+};
 use tracing::Level;
+use x509_cert::{
+    anchor::{CertPathControls, CertPolicies, TrustAnchorChoice, TrustAnchorInfo},
+    der::{
+        asn1::{OctetString, Utf8StringRef},
+        oid::{
+            db::rfc5280::{ID_KP_CLIENT_AUTH, ID_KP_SERVER_AUTH},
+            AssociatedOid,
+        },
+        Decode, Encode,
+    },
+    ext::pkix::{
+        name::GeneralName, AuthorityInfoAccessSyntax, CrlDistributionPoints, KeyUsage, KeyUsages,
+        SubjectAltName, SubjectDirectoryAttributes,
+    },
+    spki::{AlgorithmIdentifierRef, ObjectIdentifier, SubjectPublicKeyInfoOwned},
+};
 
 const ROOT_CERT: &[u8] = include_bytes!("examples/root.crt");
 const VALID_CERT_CHAIN: &[u8] = include_bytes!("examples/chain-valid.crt");
 const INVALID_CERT_CHAIN: &[u8] = include_bytes!("examples/chain-invalid.crt");
+const RSA_ROOT_CERT: &[u8] = include_bytes!("examples/rsa-root.crt");
+const RSA_REKEYED_ROOT_CERT: &[u8] = include_bytes!("examples/rsa-root-rekeyed.crt");
+const RSA_CERT_CHAIN: &[u8] = include_bytes!("examples/rsa-chain.crt");
+const RSA_1024_ROOT_CERT: &[u8] = include_bytes!("examples/rsa-1024-root.crt");
+const RSA_1024_LEAF_CERT: &[u8] = include_bytes!("examples/rsa-1024-leaf.crt");
+const RSA_EXPIRED_INTERMEDIATE_CERT: &[u8] =
+    include_bytes!("examples/rsa-intermediate-expired.crt");
+const RSA_CROSS_INTERMEDIATE_CERT: &[u8] = include_bytes!("examples/rsa-intermediate-cross.crt");
+const RSA_BRIDGE_INTERMEDIATE_CERTS: &[u8] =
+    include_bytes!("examples/rsa-bridge-intermediates.crt");
+const RSA_PSS_CERT_CHAIN: &[u8] = include_bytes!("examples/rsa-pss-chain.crt");
+const RSA_PSS_UNSUPPORTED_MGF_CERT: &[u8] = include_bytes!("examples/rsa-pss-unsupported-mgf.crt");
+const ECDSA_P256_ROOT_CERT: &[u8] = include_bytes!("examples/ecdsa-p256-root.crt");
+const ECDSA_P256_CERT_CHAIN: &[u8] = include_bytes!("examples/ecdsa-p256-chain.crt");
+const ECDSA_P384_ROOT_CERT: &[u8] = include_bytes!("examples/ecdsa-p384-root.crt");
+const ECDSA_P384_CERT_CHAIN: &[u8] = include_bytes!("examples/ecdsa-p384-chain.crt");
+const ECDSA_P521_ROOT_CERT: &[u8] = include_bytes!("examples/ecdsa-p521-root.crt");
+const ECDSA_P521_CERT_CHAIN: &[u8] = include_bytes!("examples/ecdsa-p521-chain.crt");
+const ECDSA_P521_SHA256_LEAF_CERT: &[u8] = include_bytes!("examples/ecdsa-p521-sha256-leaf.crt");
+const ECDSA_SECP256K1_ROOT_CERT: &[u8] = include_bytes!("examples/ecdsa-secp256k1-root.crt");
+const ECDSA_SECP256K1_CERT_CHAIN: &[u8] = include_bytes!("examples/ecdsa-secp256k1-chain.crt");
+const SELF_ISSUED_LOOP_CERT_CHAIN: &[u8] = include_bytes!("examples/self-issued-loop-chain.crt");
+const NAME_CONSTRAINTS_ROOT_CERT: &[u8] = include_bytes!("examples/name-constraints-root.crt");
+const NAME_CONSTRAINTS_CERT_CHAIN: &[u8] = include_bytes!("examples/name-constraints-chain.crt");
+const NAME_CONSTRAINTS_VIOLATING_LEAF_CERTS: &[u8] =
+    include_bytes!("examples/name-constraints-violating-leaves.crt");
+const NAME_CONSTRAINTS_EXCLUDED_CERT_CHAIN: &[u8] =
+    include_bytes!("examples/name-constraints-excluded-chain.crt");
+const POLICY_ROOT_CERT: &[u8] = include_bytes!("examples/policy-root.crt");
+const POLICY_MAPPING_CERT_CHAIN: &[u8] = include_bytes!("examples/policy-mapping-chain.crt");
+const POLICY_ANY_CERT_CHAIN: &[u8] = include_bytes!("examples/policy-any-chain.crt");
+const POLICY_MESH_CERT_CHAIN: &[u8] = include_bytes!("examples/policy-mesh-chain.crt");
+const EKU_ROOT_CERT: &[u8] = include_bytes!("examples/eku-root.crt");
+const EKU_CERT_CHAIN: &[u8] = include_bytes!("examples/eku-chain.crt");
+const EKU_CLIENT_LEAF_CERT: &[u8] = include_bytes!("examples/eku-client-leaf.crt");
+const IDENTITY_ROOT_CERT: &[u8] = include_bytes!("examples/identity-root.crt");
+const IDENTITY_CERT_CHAIN: &[u8] = include_bytes!("examples/identity-chain.crt");
+const IDENTITY_CN_LEAF_CERT: &[u8] = include_bytes!("examples/identity-cn-leaf.crt");
+const IDENTITY_EMAIL_URI_LEAF_CERT: &[u8] = include_bytes!("examples/identity-email-uri-leaf.crt");
+const SPIFFE_INVALID_LEAF_CERTS: &[u8] = include_bytes!("examples/spiffe-invalid-leaves.crt");
+const UNKNOWN_EXTENSIONS_LEAF_CERT: &[u8] = include_bytes!("examples/unknown-extensions-leaf.crt");
+const STANDARD_EXTENSIONS_LEAF_CERT: &[u8] =
+    include_bytes!("examples/standard-extensions-leaf.crt");
+const SLH_DSA_ROOT_CERT: &[u8] = include_bytes!("examples/slh-dsa-sha2-128s-root.crt");
+
+/// Point in time within the validity period of the example chains
+fn validation_time() -> SystemTime {
+    // 2024-01-01T00:00:00Z
+    SystemTime::UNIX_EPOCH + Duration::from_secs(1_704_067_200)
+}
+
+/// Time within the validity of the fixtures generated since 2026-10-16
+fn fixtures_time() -> SystemTime {
+    // 2027-01-01T00:00:00Z
+    SystemTime::UNIX_EPOCH + Duration::from_secs(1_798_761_600)
+}
+
+fn fixtures_options() -> ValidationOptions {
+    ValidationOptions::default().set_time_source(fixtures_time())
+}
+
+/// Example policies in the domain of the policy root, the policy CA maps the second one
+/// to 1.3.6.1.4.1.55555.1.3 in its own domain
+const POLICY_1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.55555.1.1");
+const POLICY_2: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.55555.1.2");
 
 fn init() {
     tracing_subscriber::fmt()
@@ -28,7 +130,88 @@ fn validate_chain_period() {
         .build()
         .unwrap();
 
-    assert!(chain.validate_period().is_ok());
+    assert!(chain.validate_period_at(validation_time()).is_ok());
+}
+
+#[test]
+fn validate_expired_chain_period() {
+    init();
+
+    let mut certificates = read_certs(VALID_CERT_CHAIN);
+
+    let chain = CertificateChainBuilder::default()
+        .set_leaf(certificates.pop().unwrap())
+        .set_intermediates(certificates)
+        .build()
+        .unwrap();
+
+    // 2027-01-01T00:00:00Z, after the leaf expired
+    let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_798_761_600);
+
+    assert!(matches!(
+        chain.validate_period_at(time),
+        Err(Error::Certificate(CertificateError::CertificateExpired(_)))
+    ));
+
+    // 2034-01-01T00:00:00Z, after all certificates expired,
+    // both entry points report the same first certificate
+    let time = SystemTime::UNIX_EPOCH + Duration::from_secs(2_019_686_400);
+    let options = ValidationOptions::default().set_time_source(time);
+
+    let Err(Error::Certificate(CertificateError::CertificateExpired(at))) =
+        chain.validate_period_at(time)
+    else {
+        panic!("chain must be expired");
+    };
+    let Err(Error::Certificate(CertificateError::CertificateExpired(with))) =
+        chain.validate_period_with(&options)
+    else {
+        panic!("chain must be expired");
+    };
+    assert_eq!(at, with);
+}
+
+#[test]
+fn validate_chain_period_with_tolerances() {
+    init();
+
+    let mut certificates = read_certs(VALID_CERT_CHAIN);
+
+    let chain = CertificateChainBuilder::default()
+        .set_leaf(certificates.pop().unwrap())
+        .set_intermediates(certificates)
+        .build()
+        .unwrap();
+
+    // One hour after the leaf expired (2026-05-02T12:49:41Z)
+    let expired = SystemTime::UNIX_EPOCH + Duration::from_secs(1_777_726_181 + 3600);
+
+    let options = ValidationOptions::default().set_time_source(expired);
+    assert!(matches!(
+        chain.validate_period_with(&options),
+        Err(Error::Certificate(CertificateError::CertificateExpired(elapsed)))
+            if elapsed == Duration::from_secs(3600)
+    ));
+
+    let options = options.set_not_after_grace(Duration::from_secs(2 * 3600));
+    assert!(chain.validate_period_with(&options).is_ok());
+
+    // One minute before the intermediates became valid (2023-05-03T12:43:37Z)
+    let immature = SystemTime::UNIX_EPOCH + Duration::from_secs(1_683_117_817 - 60);
+
+    let options = ValidationOptions::default().set_time_source(immature);
+    assert!(matches!(
+        chain.validate_period_with(&options),
+        Err(Error::Certificate(CertificateError::CertificateImmature(remaining)))
+            if remaining == Duration::from_secs(60)
+    ));
+
+    let options = options.set_not_before_skew(Duration::from_secs(5 * 60));
+    assert!(matches!(
+        chain.validate_period_with(&options),
+        Err(Error::Certificate(CertificateError::CertificateImmature(remaining)))
+            if remaining > Duration::from_secs(5 * 60)
+    ));
 }
 
 #[test]
@@ -44,7 +227,9 @@ fn validate_valid_chain() {
         .build()
         .unwrap();
 
-    assert!(chain.validate_path(&trust_anchor).is_ok());
+    assert!(chain
+        .validate_path_at(&trust_anchor, validation_time())
+        .is_ok());
 }
 
 #[test]
@@ -63,6 +248,668 @@ fn validate_invalid_chain() {
     assert!(chain.validate_path(&trust_anchor).is_err());
 }
 
+#[test]
+fn validate_valid_rsa_chain() {
+    init();
+
+    let trust_anchor = Certificate::from_pem(RSA_ROOT_CERT).unwrap();
+    let mut certificates = read_certs(RSA_CERT_CHAIN);
+
+    let chain = CertificateChainBuilder::default()
+        .set_leaf(certificates.pop().unwrap())
+        .set_intermediates(certificates)
+        .build()
+        .unwrap();
+
+    assert!(chain
+        .validate_path_at(&trust_anchor, fixtures_time())
+        .is_ok());
+}
+
+#[test]
+fn validate_rsa_chain_with_wrong_trust_anchor() {
+    init();
+
+    let trust_anchor = Certificate::from_pem(ROOT_CERT).unwrap();
+    let mut certificates = read_certs(RSA_CERT_CHAIN);
+
+    let chain = CertificateChainBuilder::default()
+        .set_leaf(certificates.pop().unwrap())
+        .set_intermediates(certificates)
+        .build()
+        .unwrap();
+
+    assert!(chain
+        .validate_path_at(&trust_anchor, fixtures_time())
+        .is_err());
+}
+
+#[test]
+fn validate_valid_rsa_pss_chain() {
+    init();
+
+    let trust_anchor = Certificate::from_pem(RSA_ROOT_CERT).unwrap();
+    let mut certificates = read_certs(RSA_PSS_CERT_CHAIN);
+
+    let chain = CertificateChainBuilder::default()
+        .set_leaf(certificates.pop().unwrap())
+        .set_intermediates(certificates)
+        .build()
+        .unwrap();
+
+    assert!(chain
+        .validate_path_at(&trust_anchor, fixtures_time())
+        .is_ok());
+}
+
+#[test]
+fn validate_rsa_chain_with_small_key() {
+    init();
+
+    let trust_anchor = Certificate::from_pem(RSA_1024_ROOT_CERT).unwrap();
+    let leaf = Certificate::from_pem(RSA_1024_LEAF_CERT).unwrap();
+
+    // The root has a 1024-bit key
+    let chain = CertificateChain::new(Vec::new(), leaf);
+    assert!(matches!(
+        chain.validate_path_at(&trust_anchor, fixtures_time()),
+        Err(Error::Signature(signature::Error::KeyTooSmall(1024)))
+    ));
+}
+
+#[test]
+fn validate_rsa_pss_unsupported_mgf() {
+    init();
+
+    let trust_anchor = Certificate::from_pem(RSA_ROOT_CERT).unwrap();
+    let leaf = Certificate::from_pem(RSA_PSS_UNSUPPORTED_MGF_CERT).unwrap();
+
+    let result = trust_anchor.verify_signature(&leaf);
+
+    assert!(matches!(
+        result,
+        Err(Error::Signature(signature::Error::PssParametersUnsupported))
+    ));
+}
+
+#[test]
+fn validate_valid_ecdsa_p256_chain() {
+    init();
+
+    let trust_anchor = Certificate::from_pem(ECDSA_P256_ROOT_CERT).unwrap();
+    let mut certificates = read_certs(ECDSA_P256_CERT_CHAIN);
+
+    let chain = CertificateChainBuilder::default()
+        .set_leaf(certificates.pop().unwrap())
+        .set_intermediates(certificates)
+        .build()
+        .unwrap();
+
+    assert!(chain
+        .validate_path_at(&trust_anchor, fixtures_time())
+        .is_ok());
+}
+
+#[test]
+fn validate_valid_ecdsa_p384_chain() {
+    init();
+
+    let trust_anchor = Certificate::from_pem(ECDSA_P384_ROOT_CERT).unwrap();
+    let mut certificates = read_certs(ECDSA_P384_CERT_CHAIN);
+
+    let chain = CertificateChainBuilder::default()
+        .set_leaf(certificates.pop().unwrap())
+        .set_intermediates(certificates)
+        .build()
+        .unwrap();
+
+    assert!(chain
+        .validate_path_at(&trust_anchor, fixtures_time())
+        .is_ok());
+}
+
+#[test]
+fn validate_valid_ecdsa_p521_chain() {
+    init();
+
+    let trust_anchor = Certificate::from_pem(ECDSA_P521_ROOT_CERT).unwrap();
+    let mut certificates = read_certs(ECDSA_P521_CERT_CHAIN);
+
+    let chain = CertificateChainBuilder::default()
+        .set_leaf(certificates.pop().unwrap())
+        .set_intermediates(certificates)
+        .build()
+        .unwrap();
+
+    assert!(chain
+        .validate_path_at(&trust_anchor, fixtures_time())
+        .is_ok());
+}
+
+#[test]
+fn validate_valid_ecdsa_secp256k1_chain() {
+    init();
+
+    let trust_anchor = Certificate::from_pem(ECDSA_SECP256K1_ROOT_CERT).unwrap();
+    let mut certificates = read_certs(ECDSA_SECP256K1_CERT_CHAIN);
+
+    let chain = CertificateChainBuilder::default()
+        .set_leaf(certificates.pop().unwrap())
+        .set_intermediates(certificates)
+        .build()
+        .unwrap();
+
+    assert!(chain
+        .validate_path_at(&trust_anchor, fixtures_time())
+        .is_ok());
+}
+
+#[test]
+fn verify_ecdsa_p521_with_sha256() {
+    init();
+
+    let issuer = Certificate::from_pem(ECDSA_P521_ROOT_CERT).unwrap();
+    let leaf = Certificate::from_pem(ECDSA_P521_SHA256_LEAF_CERT).unwrap();
+
+    assert!(issuer.verify_signature(&leaf).is_ok());
+}
+
+#[test]
+fn verify_ecdsa_with_wrong_curve() {
+    init();
+
+    let issuer = Certificate::from_pem(ECDSA_P384_ROOT_CERT).unwrap();
+    let certificates = read_certs(ECDSA_P256_CERT_CHAIN);
+
+    assert!(matches!(
+        issuer.verify_signature(&certificates[0]),
+        Err(Error::Signature(_))
+    ));
+}
+
+#[test]
+fn verify_ecdsa_with_rsa_key() {
+    init();
+
+    let issuer = Certificate::from_pem(RSA_ROOT_CERT).unwrap();
+    let certificates = read_certs(ECDSA_P256_CERT_CHAIN);
+
+    assert!(matches!(
+        issuer.verify_signature(&certificates[0]),
+        Err(Error::Certificate(CertificateError::AlgorithmMismatch))
+    ));
+}
+
+#[test]
+fn verify_slh_dsa_signature_unsupported() {
+    init();
+
+    let cert = Certificate::from_pem(SLH_DSA_ROOT_CERT).unwrap();
+
+    assert!(matches!(
+        cert.verify_signature(&cert),
+        Err(Error::Certificate(CertificateError::AlgorithmUnsupported))
+    ));
+}
+
+#[test]
+fn validate_chains_with_trust_store() {
+    init();
+
+    // The rekeyed root has the same subject as the RSA root but a different key
+    let trust_store = TrustStore::from_certificates(
+        [
+            ROOT_CERT,
+            RSA_REKEYED_ROOT_CERT,
+            RSA_ROOT_CERT,
+            ECDSA_P256_ROOT_CERT,
+        ]
+        .map(|pem| Certificate::from_pem(pem).unwrap()),
+    )
+    .unwrap();
+
+    let options = fixtures_options();
+
+    for (chain, root) in [
+        (RSA_CERT_CHAIN, RSA_ROOT_CERT),
+        (ECDSA_P256_CERT_CHAIN, ECDSA_P256_ROOT_CERT),
+    ] {
+        let mut certificates = read_certs(chain);
+
+        let chain = CertificateChainBuilder::default()
+            .set_leaf(certificates.pop().unwrap())
+            .set_intermediates(certificates)
+            .build()
+            .unwrap();
+
+        let path = chain.validate_path_in(&trust_store, &options).unwrap();
+
+        assert_eq!(
+            path.trust_anchor().certificate().unwrap().to_der().unwrap(),
+            Certificate::from_pem(root).unwrap().to_der().unwrap()
+        );
+    }
+}
+
+#[test]
+fn validate_chain_without_trust_anchor() {
+    init();
+
+    let trust_store =
+        TrustStore::from_certificates([Certificate::from_pem(RSA_REKEYED_ROOT_CERT).unwrap()])
+            .unwrap();
+
+    let mut certificates = read_certs(RSA_CERT_CHAIN);
+
+    let chain = CertificateChainBuilder::default()
+        .set_leaf(certificates.pop().unwrap())
+        .set_intermediates(certificates)
+        .build()
+        .unwrap();
+
+    assert!(matches!(
+        chain.validate_path_in(&trust_store, &fixtures_options()),
+        Err(Error::Certificate(CertificateError::TrustAnchorNotFound))
+    ));
+}
+
+#[test]
+fn build_path_from_unordered_pool() {
+    init();
+
+    let trust_store = TrustStore::from_certificates(
+        [RSA_ROOT_CERT, ECDSA_P256_ROOT_CERT].map(|pem| Certificate::from_pem(pem).unwrap()),
+    )
+    .unwrap();
+
+    let mut certificates = read_certs(RSA_CERT_CHAIN);
+    let leaf = certificates.pop().unwrap();
+    let intermediate = certificates.pop().unwrap();
+
+    // The expired intermediate has the same subject and key as the valid one
+    let mut pool = read_certs(ECDSA_P256_CERT_CHAIN);
+    pool.push(Certificate::from_pem(RSA_EXPIRED_INTERMEDIATE_CERT).unwrap());
+    pool.push(intermediate.clone());
+    pool.push(intermediate.clone());
+
+    let path = PathBuilder::new(&trust_store)
+        .set_options(fixtures_options())
+        .add_intermediates(pool)
+        .unwrap()
+        .build(&leaf)
+        .unwrap();
+
+    assert_eq!(path.chain().intermediates().len(), 1);
+    assert_eq!(
+        path.chain().intermediates()[0].to_der().unwrap(),
+        intermediate.to_der().unwrap()
+    );
+    assert_eq!(
+        path.trust_anchor().certificate().unwrap().to_der().unwrap(),
+        Certificate::from_pem(RSA_ROOT_CERT)
+            .unwrap()
+            .to_der()
+            .unwrap()
+    );
+}
+
+#[test]
+fn build_all_paths_with_cross_certificate() {
+    init();
+
+    let trust_store = TrustStore::from_certificates(
+        [RSA_ROOT_CERT, RSA_REKEYED_ROOT_CERT].map(|pem| Certificate::from_pem(pem).unwrap()),
+    )
+    .unwrap();
+
+    let mut certificates = read_certs(RSA_CERT_CHAIN);
+    let leaf = certificates.pop().unwrap();
+    certificates.push(Certificate::from_pem(RSA_CROSS_INTERMEDIATE_CERT).unwrap());
+
+    let paths = PathBuilder::new(&trust_store)
+        .set_options(fixtures_options())
+        .add_intermediates(certificates)
+        .unwrap()
+        .build_all(&leaf)
+        .unwrap();
+
+    assert_eq!(paths.len(), 2);
+    assert_ne!(
+        paths[0]
+            .trust_anchor()
+            .certificate()
+            .unwrap()
+            .to_der()
+            .unwrap(),
+        paths[1]
+            .trust_anchor()
+            .certificate()
+            .unwrap()
+            .to_der()
+            .unwrap()
+    );
+}
+
+#[test]
+fn build_shortest_path() {
+    init();
+
+    let trust_store =
+        TrustStore::from_certificates([Certificate::from_pem(RSA_ROOT_CERT).unwrap()]).unwrap();
+
+    let mut certificates = read_certs(RSA_CERT_CHAIN);
+    let leaf = certificates.pop().unwrap();
+    let intermediate = certificates.pop().unwrap();
+
+    // The intermediate re-issued by a bridge CA comes first, but its path is longer
+    let mut pool = read_certs(RSA_BRIDGE_INTERMEDIATE_CERTS);
+    pool.push(intermediate.clone());
+
+    let builder = PathBuilder::new(&trust_store)
+        .set_options(fixtures_options())
+        .add_intermediates(pool)
+        .unwrap();
+
+    let path = builder.build(&leaf).unwrap();
+    assert_eq!(path.chain().intermediates().len(), 1);
+    assert_eq!(
+        path.chain().intermediates()[0].to_der().unwrap(),
+        intermediate.to_der().unwrap()
+    );
+
+    let paths = builder.build_all(&leaf).unwrap();
+    assert_eq!(
+        paths
+            .iter()
+            .map(|path| path.chain().intermediates().len())
+            .collect::<Vec<_>>(),
+        [1, 2]
+    );
+}
+
+#[test]
+fn build_path_without_issuer() {
+    init();
+
+    let trust_store =
+        TrustStore::from_certificates([Certificate::from_pem(RSA_ROOT_CERT).unwrap()]).unwrap();
+
+    let leaf = read_certs(RSA_CERT_CHAIN).pop().unwrap();
+
+    assert!(matches!(
+        PathBuilder::new(&trust_store)
+            .set_options(fixtures_options())
+            .build(&leaf),
+        Err(Error::Certificate(CertificateError::TrustAnchorNotFound))
+    ));
+}
+
+#[test]
+fn build_path_with_self_issued_loop() {
+    init();
+
+    let trust_store =
+        TrustStore::from_certificates([Certificate::from_pem(RSA_ROOT_CERT).unwrap()]).unwrap();
+
+    // 20 certificates of the same CA with the same subject and key,
+    // each of them may have issued the others and the leaf
+    let mut certificates = read_certs(SELF_ISSUED_LOOP_CERT_CHAIN);
+    let leaf = certificates.pop().unwrap();
+    assert_eq!(certificates.len(), 20);
+
+    let builder = PathBuilder::new(&trust_store)
+        .set_options(fixtures_options())
+        .add_intermediates(certificates)
+        .unwrap();
+
+    // The certificates are not chained to each other, so the search ends quickly
+    assert!(matches!(
+        builder.build(&leaf),
+        Err(Error::Certificate(CertificateError::TrustAnchorNotFound))
+    ));
+
+    let builder = builder.set_max_signature_checks(10);
+    assert!(matches!(
+        builder.build(&leaf),
+        Err(Error::Certificate(
+            CertificateError::PathBuildingLimitExceeded
+        ))
+    ));
+}
+
+#[test]
+fn validate_chain_with_public_key_trust_anchor() {
+    init();
+
+    let root = Certificate::from_pem(RSA_ROOT_CERT).unwrap();
+    let tbs = &root.inner().tbs_certificate;
+    let trust_anchor =
+        TrustAnchor::from_public_key(tbs.subject.clone(), tbs.subject_public_key_info.clone());
+
+    let mut certificates = read_certs(RSA_CERT_CHAIN);
+
+    let chain = CertificateChainBuilder::default()
+        .set_leaf(certificates.pop().unwrap())
+        .set_intermediates(certificates)
+        .build()
+        .unwrap();
+
+    assert!(chain
+        .validate_path_with(&trust_anchor, &fixtures_options())
+        .is_ok());
+}
+
+#[test]
+fn validate_chain_with_trust_anchor_info() {
+    init();
+
+    let root = Certificate::from_pem(RSA_ROOT_CERT).unwrap();
+    let tbs = &root.inner().tbs_certificate;
+
+    let trust_anchor_info = |path_len_constraint| {
+        TrustAnchorChoice::TaInfo(TrustAnchorInfo {
+            version: Default::default(),
+            pub_key: tbs.subject_public_key_info.clone(),
+            key_id: OctetString::new(root.get_subject_key_identifier().unwrap().0.as_bytes())
+                .unwrap(),
+            ta_title: None,
+            cert_path: Some(CertPathControls {
+                ta_name: tbs.subject.clone(),
+                certificate: None,
+                policy_set: None,
+                policy_flags: None,
+                name_constr: None,
+                path_len_constraint: Some(path_len_constraint),
+            }),
+            extensions: None,
+            ta_title_lang_tag: None,
+        })
+    };
+
+    let mut certificates = read_certs(RSA_CERT_CHAIN);
+
+    let chain = CertificateChainBuilder::default()
+        .set_leaf(certificates.pop().unwrap())
+        .set_intermediates(certificates)
+        .build()
+        .unwrap();
+
+    let options = fixtures_options();
+
+    let trust_anchor = TrustAnchor::from_der(trust_anchor_info(1).to_der().unwrap()).unwrap();
+    assert!(chain.validate_path_with(&trust_anchor, &options).is_ok());
+
+    // The anchor doesn't allow any intermediates
+    let trust_anchor = TrustAnchor::from_der(trust_anchor_info(0).to_der().unwrap()).unwrap();
+    assert!(matches!(
+        chain.validate_path_with(&trust_anchor, &options),
+        Err(Error::Certificate(
+            CertificateError::BasicConstraintsViolation
+        ))
+    ));
+}
+
+#[test]
+fn validate_chain_with_expired_trust_anchor_certificate() {
+    init();
+
+    // The validity period of the trust anchor certificate is not checked
+    let trust_anchor =
+        TrustAnchor::from(Certificate::from_pem(RSA_EXPIRED_INTERMEDIATE_CERT).unwrap());
+
+    let chain = CertificateChain::new(Vec::new(), read_certs(RSA_CERT_CHAIN).pop().unwrap());
+
+    assert!(chain
+        .validate_path_with(&trust_anchor, &fixtures_options())
+        .is_ok());
+}
+
+#[test]
+fn validate_name_constrained_chain() {
+    init();
+
+    let trust_anchor = Certificate::from_pem(NAME_CONSTRAINTS_ROOT_CERT).unwrap();
+    let mut certificates = read_certs(NAME_CONSTRAINTS_CERT_CHAIN);
+
+    let chain = CertificateChainBuilder::default()
+        .set_leaf(certificates.pop().unwrap())
+        .set_intermediates(certificates)
+        .build()
+        .unwrap();
+
+    assert!(chain
+        .validate_path_at(&trust_anchor, fixtures_time())
+        .is_ok());
+}
+
+#[test]
+fn validate_name_constraints_violations() {
+    init();
+
+    let trust_anchor = Certificate::from_pem(NAME_CONSTRAINTS_ROOT_CERT).unwrap();
+    let intermediate = read_certs(NAME_CONSTRAINTS_CERT_CHAIN).remove(0);
+
+    // DNS name, excluded DNS name, IP address, email address, URI, subject,
+    // URI on a subdomain of the permitted URI host, common name of a leaf
+    // without DNS names and absolute DNS name outside the constraints
+    let leaves = read_certs(NAME_CONSTRAINTS_VIOLATING_LEAF_CERTS);
+    assert_eq!(leaves.len(), 9);
+
+    for leaf in leaves {
+        let chain = CertificateChainBuilder::default()
+            .set_leaf(leaf)
+            .set_intermediates([intermediate.clone()])
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            chain.validate_path_at(&trust_anchor, fixtures_time()),
+            Err(Error::Certificate(
+                CertificateError::NameConstraintsViolation
+            ))
+        ));
+    }
+}
+
+#[test]
+fn validate_excluded_names_in_other_form() {
+    init();
+
+    let trust_anchor = Certificate::from_pem(NAME_CONSTRAINTS_ROOT_CERT).unwrap();
+    let mut leaves = read_certs(NAME_CONSTRAINTS_EXCLUDED_CERT_CHAIN);
+    let intermediate = leaves.remove(0);
+
+    // The intermediate only excludes secret.tenant-a.example.com and O=Tenant B,
+    // the leaves present them as absolute DNS name with a trailing dot
+    // and as O=TENANT  B in a PrintableString
+    assert_eq!(leaves.len(), 2);
+    assert!(leaves[0]
+        .verify_hostname("secret.tenant-a.example.com")
+        .is_ok());
+
+    for leaf in leaves {
+        let chain = CertificateChainBuilder::default()
+            .set_leaf(leaf)
+            .set_intermediates([intermediate.clone()])
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            chain.validate_path_at(&trust_anchor, fixtures_time()),
+            Err(Error::Certificate(
+                CertificateError::NameConstraintsViolation
+            ))
+        ));
+    }
+}
+
+#[test]
+fn validate_chain_with_empty_registry() {
+    init();
+
+    let trust_anchor = Certificate::from_pem(RSA_ROOT_CERT).unwrap();
+    let mut certificates = read_certs(RSA_CERT_CHAIN);
+
+    let chain = CertificateChainBuilder::default()
+        .set_leaf(certificates.pop().unwrap())
+        .set_intermediates(certificates)
+        .build()
+        .unwrap();
+
+    let options = fixtures_options().set_signature_verifiers(SignatureVerifierRegistry::empty());
+
+    assert!(matches!(
+        chain.validate_path_with(&TrustAnchor::from(trust_anchor), &options),
+        Err(Error::Certificate(CertificateError::AlgorithmUnsupported))
+    ));
+}
+
+/// Verifier accepting every signature of its key algorithm
+struct CountingVerifier {
+    key_algo: ObjectIdentifier,
+    calls: Arc<AtomicUsize>,
+}
+
+impl CertificateSignatureVerifier for CountingVerifier {
+    fn verify(&self, _: &SubjectPublicKeyInfoOwned, _: &Certificate) -> pki_rs::Result<()> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn is_compatible(&self, key_algo: AlgorithmIdentifierRef<'_>, _: ObjectIdentifier) -> bool {
+        key_algo.oid == self.key_algo
+    }
+}
+
+#[test]
+fn verify_signature_with_custom_verifier() {
+    init();
+
+    let cert = Certificate::from_pem(SLH_DSA_ROOT_CERT).unwrap();
+    let algo = cert.inner().signature_algorithm.oid;
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let mut registry = SignatureVerifierRegistry::default();
+    registry.register(
+        algo,
+        CountingVerifier {
+            key_algo: algo,
+            calls: calls.clone(),
+        },
+    );
+
+    assert!(cert.verify_signature_with(&cert, &registry).is_ok());
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // Keys of other algorithms are rejected before the verifier is called
+    let issuer = Certificate::from_pem(RSA_ROOT_CERT).unwrap();
+    assert!(matches!(
+        issuer.verify_signature_with(&cert, &registry),
+        Err(Error::Certificate(CertificateError::AlgorithmMismatch))
+    ));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
 fn read_certs(mut rd: impl io::BufRead) -> Vec<Certificate> {
     rustls_pemfile::certs(&mut rd)
         .unwrap()
@@ -71,3 +918,517 @@ fn read_certs(mut rd: impl io::BufRead) -> Vec<Certificate> {
         .collect::<Result<Vec<_>, _>>()
         .unwrap()
 }
+
+#[test]
+fn validate_chain_with_policy_mapping() {
+    init();
+
+    let trust_anchor = TrustAnchor::from(Certificate::from_pem(POLICY_ROOT_CERT).unwrap());
+    let mut certificates = read_certs(POLICY_MAPPING_CERT_CHAIN);
+    let chain = CertificateChain::new(vec![certificates.remove(0)], certificates.remove(0));
+
+    // The leaf asserts the first policy and the mapped second policy
+    let validated = chain
+        .validate_path_with(&trust_anchor, &fixtures_options())
+        .unwrap();
+    assert_eq!(
+        validated.authorities_constrained_policies(),
+        &[POLICY_1, POLICY_2].into()
+    );
+    assert_eq!(
+        validated.user_constrained_policies(),
+        &[POLICY_1, POLICY_2].into()
+    );
+
+    let options = fixtures_options()
+        .set_initial_policy_set([POLICY_2])
+        .set_explicit_policy(true);
+    let validated = chain.validate_path_with(&trust_anchor, &options).unwrap();
+    assert_eq!(validated.user_constrained_policies(), &[POLICY_2].into());
+
+    // Without the mapping, only the first policy remains
+    let options = options.set_inhibit_policy_mapping(true);
+    assert!(matches!(
+        chain.validate_path_with(&trust_anchor, &options),
+        Err(Error::Certificate(CertificateError::PolicyViolation))
+    ));
+
+    let validated = chain
+        .validate_path_with(&trust_anchor, &options.set_explicit_policy(false))
+        .unwrap();
+    assert_eq!(
+        validated.authorities_constrained_policies(),
+        &[POLICY_1].into()
+    );
+    assert!(validated.user_constrained_policies().is_empty());
+
+    // Policy constraints of the trust anchor
+    let trust_anchor = trust_anchor
+        .set_policy_set([POLICY_1])
+        .set_policy_flags(CertPolicies::RequireExplicitPolicy);
+    let validated = chain
+        .validate_path_with(&trust_anchor, &fixtures_options())
+        .unwrap();
+    assert_eq!(validated.user_constrained_policies(), &[POLICY_1].into());
+
+    let options = fixtures_options().set_initial_policy_set([POLICY_2]);
+    assert!(matches!(
+        chain.validate_path_with(&trust_anchor, &options),
+        Err(Error::Certificate(CertificateError::PolicyViolation))
+    ));
+}
+
+#[test]
+fn validate_chain_with_any_policy() {
+    init();
+
+    let trust_anchor = TrustAnchor::from(Certificate::from_pem(POLICY_ROOT_CERT).unwrap());
+    let mut certificates = read_certs(POLICY_ANY_CERT_CHAIN);
+    let chain = CertificateChain::new(vec![certificates.remove(0)], certificates.remove(0));
+
+    // The intermediate asserts anyPolicy and requires an explicit policy
+    let validated = chain
+        .validate_path_with(&trust_anchor, &fixtures_options())
+        .unwrap();
+    assert_eq!(
+        validated.authorities_constrained_policies(),
+        &[POLICY_1].into()
+    );
+
+    let options = fixtures_options().set_inhibit_any_policy(true);
+    assert!(matches!(
+        chain.validate_path_with(&trust_anchor, &options),
+        Err(Error::Certificate(CertificateError::PolicyViolation))
+    ));
+
+    let options = fixtures_options().set_initial_policy_set([POLICY_2]);
+    assert!(matches!(
+        chain.validate_path_with(&trust_anchor, &options),
+        Err(Error::Certificate(CertificateError::PolicyViolation))
+    ));
+}
+
+#[test]
+fn validate_chain_with_policy_mapping_mesh() {
+    init();
+
+    let trust_anchor = TrustAnchor::from(Certificate::from_pem(POLICY_ROOT_CERT).unwrap());
+    let mut certificates = read_certs(POLICY_MESH_CERT_CHAIN);
+    let leaf = certificates.pop().unwrap();
+    let chain = CertificateChain::new(certificates, leaf);
+
+    // The first CA maps each of its 32 policies to all 32 policies of the second CA,
+    // which would grow the valid policy tree to more than 1000 nodes
+    assert!(matches!(
+        chain.validate_path_with(&trust_anchor, &fixtures_options()),
+        Err(Error::Certificate(CertificateError::PolicyViolation))
+    ));
+}
+
+#[test]
+fn validate_chain_with_extended_key_usage() {
+    init();
+
+    let trust_anchor = TrustAnchor::from(Certificate::from_pem(EKU_ROOT_CERT).unwrap());
+    let mut certificates = read_certs(EKU_CERT_CHAIN);
+    let intermediate = certificates.remove(0);
+
+    // The intermediate is restricted to client authentication, the leaf is not
+    let chain = CertificateChain::new(vec![intermediate.clone()], certificates.remove(0));
+
+    let options = fixtures_options().set_required_extended_key_usages([ID_KP_SERVER_AUTH]);
+    assert!(chain.validate_path_with(&trust_anchor, &options).is_ok());
+
+    let options = options.set_extended_key_usage_chaining(true);
+    assert!(matches!(
+        chain.validate_path_with(&trust_anchor, &options),
+        Err(Error::Certificate(
+            CertificateError::ExtendedKeyUsageViolation(oid)
+        )) if oid == ID_KP_SERVER_AUTH
+    ));
+
+    let options = options.set_required_extended_key_usages([ID_KP_CLIENT_AUTH]);
+    assert!(chain.validate_path_with(&trust_anchor, &options).is_ok());
+
+    // Client authentication only leaf
+    let chain = CertificateChain::new(
+        vec![intermediate],
+        Certificate::from_pem(EKU_CLIENT_LEAF_CERT).unwrap(),
+    );
+
+    let options = fixtures_options().set_required_extended_key_usages([ID_KP_SERVER_AUTH]);
+    assert!(matches!(
+        chain.validate_path_with(&trust_anchor, &options),
+        Err(Error::Certificate(
+            CertificateError::ExtendedKeyUsageViolation(oid)
+        )) if oid == ID_KP_SERVER_AUTH
+    ));
+
+    let options = fixtures_options().set_required_extended_key_usages([ID_KP_CLIENT_AUTH]);
+    assert!(chain.validate_path_with(&trust_anchor, &options).is_ok());
+}
+
+#[test]
+fn validate_leaf_key_usage_for_purposes() {
+    init();
+
+    let trust_anchor = TrustAnchor::from(Certificate::from_pem(EKU_ROOT_CERT).unwrap());
+    let mut certificates = read_certs(EKU_CERT_CHAIN);
+    let chain = CertificateChain::new(vec![certificates.remove(0)], certificates.remove(0));
+
+    // The leaf asserts digitalSignature and keyEncipherment
+    let options = fixtures_options().set_key_purposes([
+        KeyPurpose::TlsEphemeralKeyExchange,
+        KeyPurpose::TlsRsaKeyTransport,
+    ]);
+    assert!(chain.validate_path_with(&trust_anchor, &options).is_ok());
+
+    for purpose in [KeyPurpose::DocumentSigning, KeyPurpose::CrlSigning] {
+        let options = fixtures_options().set_key_purposes([purpose]);
+        assert!(matches!(
+            chain.validate_path_with(&trust_anchor, &options),
+            Err(Error::Certificate(CertificateError::KeyUsageViolation))
+        ));
+    }
+
+    let policy = KeyUsagePolicy::default().set_requirement(
+        KeyPurpose::DocumentSigning,
+        KeyUsage(KeyUsages::DigitalSignature.into()),
+    );
+    let options = fixtures_options()
+        .set_key_purposes([KeyPurpose::DocumentSigning])
+        .set_key_usage_policy(policy);
+    assert!(chain.validate_path_with(&trust_anchor, &options).is_ok());
+}
+
+#[test]
+fn verify_leaf_hostname() {
+    init();
+
+    let trust_anchor = Certificate::from_pem(IDENTITY_ROOT_CERT).unwrap();
+    let mut certificates = read_certs(IDENTITY_CERT_CHAIN);
+    let leaf = certificates.pop().unwrap();
+    let chain = CertificateChain::new(certificates, leaf.clone());
+    assert!(chain
+        .validate_path_at(&trust_anchor, fixtures_time())
+        .is_ok());
+
+    for hostname in [
+        "leaf.example.com",
+        "LEAF.example.COM.",
+        "a.wild.example.com",
+        "bücher.example",
+        "xn--bcher-kva.example",
+    ] {
+        assert!(leaf.verify_hostname(hostname).is_ok(), "{}", hostname);
+    }
+
+    // The wildcard matches exactly one label, partial wildcards and wildcards
+    // followed by a single label are not permitted
+    for hostname in [
+        "example.com",
+        "wild.example.com",
+        "a.b.wild.example.com",
+        "foo.example.com",
+        "fo.example.com",
+        "other.example.com",
+    ] {
+        assert!(
+            matches!(
+                leaf.verify_hostname(hostname),
+                Err(Error::Certificate(CertificateError::IdentityMismatch))
+            ),
+            "{}",
+            hostname
+        );
+    }
+
+    // Invalid A-label
+    assert!(matches!(
+        leaf.verify_hostname("xn--a.example.com"),
+        Err(Error::Certificate(CertificateError::IdentityInvalid))
+    ));
+
+    assert!(leaf.verify_hostname("192.0.2.1").is_ok());
+    assert!(leaf
+        .verify_ip_address(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)))
+        .is_ok());
+    assert!(leaf
+        .verify_ip_address("2001:db8::1".parse::<Ipv6Addr>().unwrap().into())
+        .is_ok());
+    assert!(matches!(
+        leaf.verify_ip_address(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 2))),
+        Err(Error::Certificate(CertificateError::IdentityMismatch))
+    ));
+}
+
+#[test]
+fn verify_leaf_hostname_with_common_name_fallback() {
+    init();
+
+    // The leaf has no subject alternative name
+    let leaf = Certificate::from_pem(IDENTITY_CN_LEAF_CERT).unwrap();
+
+    assert!(matches!(
+        leaf.verify_hostname("cn.example.com"),
+        Err(Error::Certificate(CertificateError::IdentityMismatch))
+    ));
+
+    assert!(leaf.verify_hostname_with("cn.example.com", true).is_ok());
+
+    // The common name is ignored if the certificate has DNS names
+    let leaf = read_certs(IDENTITY_CERT_CHAIN).pop().unwrap();
+    assert!(leaf.verify_hostname_with("leaf.example.com", true).is_ok());
+}
+
+#[test]
+fn verify_leaf_email_and_uri() {
+    init();
+
+    let trust_anchor = Certificate::from_pem(IDENTITY_ROOT_CERT).unwrap();
+    let intermediate = read_certs(IDENTITY_CERT_CHAIN).remove(0);
+    let leaf = Certificate::from_pem(IDENTITY_EMAIL_URI_LEAF_CERT).unwrap();
+    let chain = CertificateChain::new(vec![intermediate], leaf.clone());
+    assert!(chain
+        .validate_path_at(&trust_anchor, fixtures_time())
+        .is_ok());
+
+    // rfc822Name and SmtpUTF8Mailbox
+    for email in [
+        "Alice@Example.com",
+        "Alice@example.COM",
+        "用户@bücher.example",
+        "用户@xn--bcher-kva.example",
+    ] {
+        assert!(leaf.verify_email(email).is_ok(), "{}", email);
+    }
+
+    for email in ["alice@example.com", "bob@example.com", "用户@example.com"] {
+        assert!(
+            matches!(
+                leaf.verify_email(email),
+                Err(Error::Certificate(CertificateError::IdentityMismatch))
+            ),
+            "{}",
+            email
+        );
+    }
+
+    assert!(matches!(
+        leaf.verify_email("example.com"),
+        Err(Error::Certificate(CertificateError::IdentityInvalid))
+    ));
+
+    assert!(leaf.verify_uri("spiffe://example.org/service/web").is_ok());
+
+    for uri in [
+        "spiffe://example.org/service/api",
+        "spiffe://example.org/service/web/",
+        "spiffe://EXAMPLE.org/service/web",
+        "https://example.org/service/web",
+    ] {
+        assert!(
+            matches!(
+                leaf.verify_uri(uri),
+                Err(Error::Certificate(CertificateError::IdentityMismatch))
+            ),
+            "{}",
+            uri
+        );
+    }
+
+    assert!(matches!(
+        leaf.verify_uri("example.org/service/web"),
+        Err(Error::Certificate(CertificateError::IdentityInvalid))
+    ));
+}
+
+#[test]
+fn validate_spiffe_svid() {
+    init();
+
+    let trust_anchor = Certificate::from_pem(IDENTITY_ROOT_CERT).unwrap();
+    let intermediate = read_certs(IDENTITY_CERT_CHAIN).remove(0);
+    let leaf = Certificate::from_pem(IDENTITY_EMAIL_URI_LEAF_CERT).unwrap();
+    let chain = CertificateChain::new(vec![intermediate.clone()], leaf);
+
+    let mut bundles = TrustBundleSet::new();
+    bundles
+        .add(
+            "example.org",
+            TrustStore::from_certificates([trust_anchor.clone()]).unwrap(),
+        )
+        .unwrap();
+
+    let validated = chain.validate_svid(&bundles, &fixtures_options()).unwrap();
+    assert_eq!(validated.spiffe_id().trust_domain(), "example.org");
+    assert_eq!(validated.spiffe_id().path(), "/service/web");
+    assert_eq!(
+        validated.spiffe_id(),
+        &SpiffeId::parse("spiffe://example.org/service/web").unwrap()
+    );
+
+    // No trust bundle for the trust domain of the leaf
+    let mut other_bundles = TrustBundleSet::new();
+    other_bundles
+        .add(
+            "example.com",
+            TrustStore::from_certificates([trust_anchor]).unwrap(),
+        )
+        .unwrap();
+    assert!(matches!(
+        chain.validate_svid(&other_bundles, &fixtures_options()),
+        Err(Error::Certificate(CertificateError::TrustAnchorNotFound))
+    ));
+
+    // Leaf without URI, with two URIs, CA leaf, leaf with keyCertSign and invalid path
+    let mut leaves = vec![read_certs(IDENTITY_CERT_CHAIN).pop().unwrap()];
+    leaves.extend(read_certs(SPIFFE_INVALID_LEAF_CERTS));
+    assert_eq!(leaves.len(), 5);
+
+    let errors: Vec<_> = leaves
+        .into_iter()
+        .map(|leaf| {
+            CertificateChain::new(vec![intermediate.clone()], leaf)
+                .validate_svid(&bundles, &fixtures_options())
+                .unwrap_err()
+        })
+        .collect();
+    assert!(matches!(
+        errors.as_slice(),
+        [
+            Error::Certificate(CertificateError::SpiffeIdInvalid),
+            Error::Certificate(CertificateError::SpiffeIdInvalid),
+            Error::Certificate(CertificateError::BasicConstraintsViolation),
+            Error::Certificate(CertificateError::KeyUsageViolation),
+            Error::Certificate(CertificateError::SpiffeIdInvalid),
+        ]
+    ));
+
+    for id in [
+        "https://example.org/service",
+        "spiffe://Example.org/service",
+        "spiffe://example.org:8443/service",
+        "spiffe://example.org/service/",
+        "spiffe://example.org//service",
+        "spiffe://example.org/service?query",
+        "spiffe:///service",
+    ] {
+        assert!(SpiffeId::parse(id).is_err(), "{}", id);
+    }
+}
+
+#[test]
+fn validate_chain_with_unprocessed_critical_extension() {
+    init();
+
+    let critical = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.55555.2.1");
+    let non_critical = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.55555.2.2");
+
+    let trust_anchor = TrustAnchor::from(Certificate::from_pem(IDENTITY_ROOT_CERT).unwrap());
+    let intermediate = read_certs(IDENTITY_CERT_CHAIN).remove(0);
+
+    // Unsupported extensions don't prevent the certificate from being parsed
+    let leaf = Certificate::from_pem(UNKNOWN_EXTENSIONS_LEAF_CERT).unwrap();
+    assert_eq!(leaf.unsupported_critical_extensions(), &[critical]);
+    assert!(leaf
+        .extensions()
+        .iter()
+        .any(|ext| ext.extn_id == non_critical && !ext.critical));
+
+    let chain = CertificateChain::new(vec![intermediate], leaf);
+
+    assert!(matches!(
+        chain.validate_path_with(&trust_anchor, &fixtures_options()),
+        Err(Error::Certificate(
+            CertificateError::UnprocessedCriticalExtensions(oids)
+        )) if oids == [critical]
+    ));
+
+    let options = fixtures_options().set_handled_extensions([critical]);
+    assert!(chain.validate_path_with(&trust_anchor, &options).is_ok());
+}
+
+#[test]
+fn trust_anchor_with_unsupported_critical_extension() {
+    init();
+
+    let cert = Certificate::from_pem(UNKNOWN_EXTENSIONS_LEAF_CERT).unwrap();
+    let tbs = TrustAnchorChoice::TbsCertificate(cert.inner().tbs_certificate.clone());
+
+    // The unsupported extension is ignored like for a trust anchor certificate
+    let from_tbs = TrustAnchor::from_der(tbs.to_der().unwrap()).unwrap();
+    let from_cert = TrustAnchor::from(&cert);
+    assert_eq!(from_tbs.name(), from_cert.name());
+    assert_eq!(from_tbs.public_key(), from_cert.public_key());
+    assert_eq!(from_tbs.key_identifier(), from_cert.key_identifier());
+}
+
+#[test]
+fn get_raw_and_typed_extensions() {
+    init();
+
+    let leaf = Certificate::from_pem(UNKNOWN_EXTENSIONS_LEAF_CERT).unwrap();
+
+    let vendor = leaf
+        .extension(ObjectIdentifier::new_unwrap("1.3.6.1.4.1.55555.2.2"))
+        .unwrap();
+    assert!(!vendor.critical);
+    assert_eq!(
+        Utf8StringRef::from_der(vendor.extn_value.as_bytes())
+            .unwrap()
+            .as_str(),
+        "non-critical"
+    );
+
+    let san = leaf.get::<SubjectAltName>().unwrap().unwrap();
+    assert!(matches!(
+        san.0.as_slice(),
+        [GeneralName::DnsName(name)] if name.as_str() == "leaf.example.com"
+    ));
+
+    assert!(leaf.get::<AuthorityInfoAccessSyntax>().unwrap().is_none());
+}
+
+#[test]
+fn validate_chain_with_standard_extensions() {
+    init();
+
+    let trust_anchor = TrustAnchor::from(Certificate::from_pem(IDENTITY_ROOT_CERT).unwrap());
+    let intermediate = read_certs(IDENTITY_CERT_CHAIN).remove(0);
+
+    // CRL distribution points, authority information access
+    // and subject directory attributes are critical
+    let leaf = Certificate::from_pem(STANDARD_EXTENSIONS_LEAF_CERT).unwrap();
+    let critical = [
+        CrlDistributionPoints::OID,
+        AuthorityInfoAccessSyntax::OID,
+        SubjectDirectoryAttributes::OID,
+    ];
+    assert_eq!(leaf.unsupported_critical_extensions(), critical);
+
+    assert_eq!(leaf.get_crl_distribution_points().unwrap().0.len(), 1);
+    assert_eq!(leaf.get_freshest_crl().unwrap().0.len(), 1);
+    assert_eq!(leaf.get_authority_info_access().unwrap().0.len(), 2);
+    assert_eq!(leaf.get_subject_info_access().unwrap().0.len(), 1);
+    assert!(matches!(
+        leaf.get_issuer_alt_name().unwrap().0.as_slice(),
+        [GeneralName::DnsName(name)] if name.as_str() == "ca.example.com"
+    ));
+    assert_eq!(leaf.get_subject_directory_attributes().unwrap().0.len(), 1);
+
+    let period = leaf.get_private_key_usage_period().unwrap();
+    assert!(period.not_before.is_some() && period.not_after.is_some());
+
+    // The extensions are decoded, but not processed by the path validation
+    let chain = CertificateChain::new(vec![intermediate], leaf);
+    assert!(matches!(
+        chain.validate_path_with(&trust_anchor, &fixtures_options()),
+        Err(Error::Certificate(
+            CertificateError::UnprocessedCriticalExtensions(_)
+        ))
+    ));
+
+    let options = fixtures_options().set_handled_extensions(critical);
+    assert!(chain.validate_path_with(&trust_anchor, &options).is_ok());
+}